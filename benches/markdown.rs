@@ -0,0 +1,120 @@
+//! Post rendering benchmarks. Run with `cargo bench --features server`.
+//!
+//! Rendered HTML is cached in the database (`post.body_html`, written on save
+//! and by `render-posts`) rather than in process memory, so it survives
+//! restarts and is shared between instances; it stays valid until the body
+//! changes.
+//!
+//! - `render_uncached` is a full `process_markdown` run, what a post without
+//!   stored HTML costs; `render_cached` is what a view pays once it has it.
+//! - `rss_uncached` and `rss_cached` build the same ten-post RSS feed, once
+//!   rendering every body and once from the stored HTML.
+//! - `syntect_defaults` is what `process_markdown` used to pay on every call
+//!   before the syntax and theme sets were loaded once.
+
+use blog::ssr::server_utils::{process_markdown, render_post_body, rss_channel, FeedContents};
+use blog::ssr::types::Post;
+use criterion::{criterion_group, criterion_main, Criterion};
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
+
+const FEED_SIZE: usize = 10;
+
+const POST_BODY: &str = r#"
+# Simulating a Geometric Brownian Motion
+
+The process follows $dS_t = \mu S_t\,dt + \sigma S_t\,dW_t$, with the closed form
+
+$$
+S_t = S_0 \exp\left(\left(\mu - \frac{\sigma^2}{2}\right)t + \sigma W_t\right)
+$$
+
+![paths](https://static.rust-dd.com/gbm.png)
+
+```rust
+use rand_distr::{Distribution, Normal};
+
+pub fn gbm(s0: f64, mu: f64, sigma: f64, dt: f64, n: usize) -> Vec<f64> {
+    let normal = Normal::new(0.0, dt.sqrt()).unwrap();
+    let mut rng = rand::thread_rng();
+    let mut path = Vec::with_capacity(n + 1);
+    path.push(s0);
+    for i in 0..n {
+        let dw = normal.sample(&mut rng);
+        path.push(path[i] * ((mu - 0.5 * sigma * sigma) * dt + sigma * dw).exp());
+    }
+    path
+}
+```
+
+| paths | time (ms) |
+|-------|-----------|
+| 1k    | 3.1       |
+| 100k  | 290.4     |
+
+- [x] Euler–Maruyama
+- [ ] Milstein
+
+```toml
+[dependencies]
+rand = "0.8"
+rand_distr = "0.4"
+```
+"#;
+
+fn feed(posts: Vec<Post>) -> FeedContents {
+    FeedContents {
+        title: "Example".to_string(),
+        link: "https://blog.example.com".to_string(),
+        description: "Example blog".to_string(),
+        posts,
+    }
+}
+
+fn bench_markdown(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+    c.bench_function("syntect_defaults", |b| {
+        b.iter(|| (SyntaxSet::load_defaults_newlines(), ThemeSet::load_defaults()))
+    });
+
+    c.bench_function("render_uncached", |b| {
+        b.iter(|| runtime.block_on(process_markdown(POST_BODY.to_string())).unwrap())
+    });
+
+    let rendered = runtime.block_on(process_markdown(POST_BODY.to_string())).unwrap();
+    let post = Post {
+        body: POST_BODY.to_string(),
+        body_html: Some(rendered.html),
+        toc: Some(rendered.toc),
+        created_at: "2024-03-01T09:00:00Z".to_string(),
+        ..Post::default()
+    };
+    c.bench_function("render_cached", |b| {
+        b.iter(|| runtime.block_on(render_post_body(&post)).unwrap())
+    });
+
+    let posts = vec![post; FEED_SIZE];
+    c.bench_function("rss_uncached", |b| {
+        b.iter(|| {
+            let mut posts = posts.clone();
+            for post in &mut posts {
+                post.body = runtime.block_on(process_markdown(post.body.clone())).unwrap().html;
+            }
+            rss_channel(&feed(posts))
+        })
+    });
+
+    c.bench_function("rss_cached", |b| {
+        b.iter(|| {
+            let mut posts = posts.clone();
+            for post in &mut posts {
+                post.body = runtime.block_on(render_post_body(post)).unwrap().html;
+            }
+            rss_channel(&feed(posts))
+        })
+    });
+}
+
+criterion_group!(benches, bench_markdown);
+criterion_main!(benches);
@@ -3,7 +3,10 @@ use dioxus::prelude::*;
 
 use crate::{
     components::{header, icons, loader},
-    pages::{home, opensource, post, projects},
+    pages::{
+        admin, author, contact, home, newsletter, opensource, post, project, projects, reference, references, search,
+        tag,
+    },
     seo,
 };
 
@@ -14,10 +17,45 @@ pub enum Route {
     Home {},
     #[route("/post/:slug")]
     Post { slug: String },
+    #[route("/tag/:tag?:page")]
+    Tag { tag: String, page: Option<usize> },
+    #[route("/author/:id")]
+    Author { id: String },
+    #[route("/search?:q")]
+    Search { q: String },
     #[route("/projects")]
     Projects {},
+    #[route("/projects/:name")]
+    Project { name: String },
+    #[route("/references?:category&:year")]
+    References {
+        category: Option<String>,
+        year: Option<String>,
+    },
+    #[route("/references/:id")]
+    Reference { id: String },
     #[route("/opensource")]
     OpenSource {},
+    #[route("/contact")]
+    Contact {},
+    #[route("/newsletter/confirm?:token")]
+    NewsletterConfirm { token: String },
+    #[route("/newsletter/unsubscribe?:token")]
+    NewsletterUnsubscribe { token: String },
+    #[nest("/admin")]
+        #[layout(AdminLayout)]
+        #[route("/")]
+        AdminPosts {},
+        #[route("/posts/new")]
+        AdminNewPost {},
+        #[route("/posts/:id")]
+        AdminEditPost { id: String },
+        #[route("/comments")]
+        AdminComments {},
+        #[route("/analytics")]
+        AdminAnalytics {},
+        #[end_layout]
+    #[end_nest]
     #[end_layout]
     #[route("/:..route")]
     PageNotFound { route: Vec<String> },
@@ -25,6 +63,14 @@ pub enum Route {
 
 #[component]
 pub fn App() -> Element {
+    let site = use_server_cached(seo::SiteConfig::current);
+    let site = use_context_provider(|| site);
+    let structured_data = seo::json_ld(vec![site.organization_ld(), site.website_ld()]);
+    let keywords = format!(
+        "{}, rust, ai, mathematics, embedded, web, systems, programming",
+        site.short_name.to_lowercase()
+    );
+
     rsx! {
         document::Stylesheet { href: asset!("/assets/tailwind.css") }
         document::Stylesheet { href: "/katex.min.css" }
@@ -32,28 +78,41 @@ pub fn App() -> Element {
         document::Meta { name: "viewport", content: "width=device-width, initial-scale=1" }
         document::Meta {
             name: "keywords",
-            content: "rust-dd, rust, ai, mathematics, embedded, web, systems, programming"
+            content: "{keywords}"
         }
         document::Meta { name: "theme-color", content: "#fafaf9" }
-        document::Meta { property: "og:site_name", content: seo::SITE_NAME }
+        document::Meta { property: "og:site_name", content: "{site.name}" }
         document::Meta { property: "og:locale", content: "en_US" }
         document::Meta {
             property: "og:image",
-            content: seo::DEFAULT_OG_IMAGE
+            content: "{site.default_og_image}"
         }
         document::Meta { property: "og:image:type", content: "image/png" }
         document::Meta { property: "og:image:width", content: "1200" }
         document::Meta { property: "og:image:height", content: "627" }
-        document::Meta { name: "twitter:site", content: seo::X_HANDLE }
-        document::Meta { name: "twitter:creator", content: seo::X_HANDLE }
-        document::Meta { name: "twitter:image", content: seo::DEFAULT_OG_IMAGE }
-        document::Meta { name: "twitter:image:alt", content: "Rust-DD logo" }
+        document::Meta { name: "twitter:site", content: "{site.x_handle}" }
+        document::Meta { name: "twitter:creator", content: "{site.x_handle}" }
+        document::Meta { name: "twitter:image", content: "{site.default_og_image}" }
+        document::Meta { name: "twitter:image:alt", content: "{site.short_name} logo" }
         document::Link {
             rel: "alternate",
             r#type: "application/rss+xml",
-            title: "Rust-DD RSS Feed",
-            href: seo::absolute_url("/rss.xml")
+            title: "{site.short_name} RSS Feed",
+            href: site.absolute_url("/rss.xml")
+        }
+        document::Link {
+            rel: "alternate",
+            r#type: "application/atom+xml",
+            title: "{site.short_name} Atom Feed",
+            href: site.absolute_url("/atom.xml")
+        }
+        document::Link {
+            rel: "alternate",
+            r#type: "application/feed+json",
+            title: "{site.short_name} JSON Feed",
+            href: site.absolute_url("/feed.json")
         }
+        document::Script { r#type: "application/ld+json", "{structured_data}" }
         document::Link { rel: "preconnect", href: "https://fonts.googleapis.com" }
         document::Link { rel: "preconnect", href: "https://fonts.gstatic.com" }
         document::Link {
@@ -111,16 +170,91 @@ fn Post(slug: String) -> Element {
     rsx! { post::Component { slug } }
 }
 
+#[component]
+fn Tag(tag: String, page: Option<usize>) -> Element {
+    rsx! { tag::Component { tag, page } }
+}
+
+#[component]
+fn Author(id: String) -> Element {
+    rsx! { author::Component { id } }
+}
+
+#[component]
+fn Search(q: String) -> Element {
+    rsx! { search::Component { q } }
+}
+
 #[component]
 fn Projects() -> Element {
     rsx! { projects::Component {} }
 }
 
+#[component]
+fn Project(name: String) -> Element {
+    rsx! { project::Component { name } }
+}
+
+#[component]
+fn References(category: Option<String>, year: Option<String>) -> Element {
+    rsx! { references::Component { category, year } }
+}
+
+#[component]
+fn Reference(id: String) -> Element {
+    rsx! { reference::Component { id } }
+}
+
 #[component]
 fn OpenSource() -> Element {
     rsx! { opensource::Component {} }
 }
 
+#[component]
+fn Contact() -> Element {
+    rsx! { contact::Component {} }
+}
+
+#[component]
+fn NewsletterConfirm(token: String) -> Element {
+    rsx! { newsletter::Component { action: newsletter::Action::Confirm, token } }
+}
+
+#[component]
+fn NewsletterUnsubscribe(token: String) -> Element {
+    rsx! { newsletter::Component { action: newsletter::Action::Unsubscribe, token } }
+}
+
+#[component]
+fn AdminLayout() -> Element {
+    rsx! { admin::Layout {} }
+}
+
+#[component]
+fn AdminPosts() -> Element {
+    rsx! { admin::posts::Component {} }
+}
+
+#[component]
+fn AdminNewPost() -> Element {
+    rsx! { admin::editor::Component { id: None } }
+}
+
+#[component]
+fn AdminEditPost(id: String) -> Element {
+    rsx! { admin::editor::Component { id: Some(id) } }
+}
+
+#[component]
+fn AdminComments() -> Element {
+    rsx! { admin::comments::Component {} }
+}
+
+#[component]
+fn AdminAnalytics() -> Element {
+    rsx! { admin::analytics::Component {} }
+}
+
 #[component]
 fn PageNotFound(route: Vec<String>) -> Element {
     let attempted_path = if route.is_empty() {
@@ -128,20 +262,23 @@ fn PageNotFound(route: Vec<String>) -> Element {
     } else {
         format!("/{}", route.join("/"))
     };
-    let canonical = seo::absolute_url(&attempted_path);
+    let site = seo::use_site();
+    let canonical = site.absolute_url(&attempted_path);
+    let title = site.title("404");
+    let description = format!("This page could not be found on {}.", site.short_name);
 
     rsx! {
-        document::Title { "404 | Rust-DD" }
-        document::Meta { name: "description", content: "This page could not be found on Rust-DD." }
+        document::Title { "{title}" }
+        document::Meta { name: "description", content: "{description}" }
         document::Meta { name: "robots", content: "noindex, nofollow" }
         document::Meta { name: "googlebot", content: "noindex, nofollow" }
         document::Meta { property: "og:type", content: "website" }
-        document::Meta { property: "og:title", content: "404 | Rust-DD" }
-        document::Meta { property: "og:description", content: "This page could not be found on Rust-DD." }
+        document::Meta { property: "og:title", content: "{title}" }
+        document::Meta { property: "og:description", content: "{description}" }
         document::Meta { property: "og:url", content: "{canonical}" }
         document::Meta { name: "twitter:card", content: "summary" }
-        document::Meta { name: "twitter:title", content: "404 | Rust-DD" }
-        document::Meta { name: "twitter:description", content: "This page could not be found on Rust-DD." }
+        document::Meta { name: "twitter:title", content: "{title}" }
+        document::Meta { name: "twitter:description", content: "{description}" }
         document::Meta { name: "twitter:url", content: "{canonical}" }
         document::Link { rel: "canonical", href: "{canonical}" }
         section { class: "mx-auto max-w-3xl text-center pt-24",
@@ -1,4 +1,7 @@
+pub mod comments;
 pub mod header;
 pub mod icons;
 pub mod loader;
+pub mod subscribe;
+pub mod tech_stack;
 pub mod theme_toggle;
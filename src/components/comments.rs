@@ -0,0 +1,203 @@
+use dioxus::prelude::*;
+use surrealdb_types::ToSql;
+
+use crate::ssr::{
+    comments::{select_comments, submit_comment},
+    types::{Comment, CommentInput},
+};
+
+const INPUT_CLASS: &str = "rounded border border-border bg-bg px-3 py-2 text-sm text-fg placeholder:text-faint focus:border-accent focus:outline-none";
+
+const COMMENT_BODY_CLASS: &str = "prose prose-sm max-w-none break-words font-sans prose-p:my-2 prose-pre:rounded prose-pre:px-3 prose-pre:py-2 prose-pre:overflow-x-auto prose-code:bg-surface-2 prose-code:px-1 prose-code:rounded prose-code:font-normal prose-code:before:content-none prose-code:after:content-none prose-a:break-all";
+
+#[component]
+pub fn Component(slug: String) -> Element {
+    let comments = use_server_future(use_reactive!(|(slug)| async move { select_comments(slug).await }))?;
+    let reply_to = use_signal(|| None::<(String, String)>);
+
+    let comments = match comments.read().as_ref() {
+        Some(Ok(comments)) => comments.clone(),
+        _ => Vec::new(),
+    };
+    let threads = comments
+        .iter()
+        .filter(|comment| comment.parent.is_none())
+        .map(|comment| {
+            let replies = comments
+                .iter()
+                .filter(|reply| reply.parent.as_ref() == Some(&comment.id))
+                .cloned()
+                .collect::<Vec<_>>();
+            (comment.clone(), replies)
+        })
+        .collect::<Vec<_>>();
+
+    rsx! {
+        section { id: "comments", class: "mt-8 rounded-lg border border-border bg-surface p-4 sm:p-5",
+            p { class: "text-xs text-faint", "// comments ({comments.len()})" }
+            if threads.is_empty() {
+                p { class: "mt-2 text-sm text-muted", "No comments yet. Start the discussion." }
+            }
+            div { class: "mt-3 divide-y divide-border",
+                for (comment, replies) in threads {
+                    div { key: "{comment.id.key.to_sql()}", class: "py-3",
+                        CommentItem { comment: comment.clone(), reply_to }
+                        if !replies.is_empty() {
+                            div { class: "mt-2 ml-3 flex flex-col gap-3 border-l border-dashed border-border pl-4",
+                                for reply in replies {
+                                    CommentItem { key: "{reply.id.key.to_sql()}", comment: reply, reply_to }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            CommentForm { slug, reply_to }
+        }
+    }
+}
+
+#[component]
+fn CommentItem(comment: Comment, reply_to: Signal<Option<(String, String)>>) -> Element {
+    let key = comment.parent.as_ref().unwrap_or(&comment.id).key.to_sql();
+    let author = comment.author_name.clone();
+
+    rsx! {
+        article {
+            p { class: "text-xs text-muted",
+                span { class: "text-fg", "{comment.author_name}" }
+                " · {comment.created_at} · "
+                button {
+                    class: "transition-colors duration-200 hover:text-accent",
+                    onclick: move |_| reply_to.set(Some((key.clone(), author.clone()))),
+                    "reply"
+                }
+            }
+            div { class: COMMENT_BODY_CLASS, dangerous_inner_html: "{comment.body_html}" }
+        }
+    }
+}
+
+#[component]
+fn CommentForm(slug: String, reply_to: Signal<Option<(String, String)>>) -> Element {
+    let mut author_name = use_signal(String::new);
+    let mut email = use_signal(String::new);
+    let mut body = use_signal(String::new);
+    let mut website = use_signal(String::new);
+    let mut status = use_signal(|| None::<Result<(), String>>);
+    let mut pending = use_signal(|| false);
+
+    let submit = move |evt: FormEvent| {
+        let slug = slug.clone();
+        async move {
+            evt.prevent_default();
+            if pending() {
+                return;
+            }
+            pending.set(true);
+            let result = submit_comment(
+                slug,
+                CommentInput {
+                    author_name: author_name(),
+                    email: email(),
+                    body: body(),
+                    parent: reply_to().map(|(key, _)| key),
+                    website: website(),
+                },
+            )
+            .await;
+            pending.set(false);
+            match result {
+                Ok(()) => {
+                    body.set(String::new());
+                    reply_to.set(None);
+                    status.set(Some(Ok(())));
+                }
+                Err(err) => status.set(Some(Err(err.to_string()))),
+            }
+        }
+    };
+
+    rsx! {
+        form { class: "mt-4 flex flex-col gap-3 border-t border-dashed border-border pt-4", onsubmit: submit,
+            if let Some((_, author)) = reply_to() {
+                p { class: "text-xs text-muted",
+                    "replying to "
+                    span { class: "text-fg", "{author}" }
+                    " · "
+                    button {
+                        r#type: "button",
+                        class: "transition-colors duration-200 hover:text-accent",
+                        onclick: move |_| reply_to.set(None),
+                        "cancel"
+                    }
+                }
+            }
+            div { class: "grid gap-3 sm:grid-cols-2",
+                label { class: "flex flex-col gap-1 text-xs text-muted",
+                    "name"
+                    input {
+                        class: INPUT_CLASS,
+                        name: "name",
+                        required: true,
+                        maxlength: 100,
+                        autocomplete: "name",
+                        value: "{author_name}",
+                        oninput: move |evt| author_name.set(evt.value()),
+                    }
+                }
+                label { class: "flex flex-col gap-1 text-xs text-muted",
+                    "email (optional, never shown)"
+                    input {
+                        class: INPUT_CLASS,
+                        r#type: "email",
+                        name: "email",
+                        autocomplete: "email",
+                        value: "{email}",
+                        oninput: move |evt| email.set(evt.value()),
+                    }
+                }
+            }
+            // Honeypot: hidden from people and assistive tech, filled in by bots.
+            label { class: "hidden", aria_hidden: "true",
+                "website"
+                input {
+                    name: "website",
+                    tabindex: "-1",
+                    autocomplete: "off",
+                    value: "{website}",
+                    oninput: move |evt| website.set(evt.value()),
+                }
+            }
+            label { class: "flex flex-col gap-1 text-xs text-muted",
+                "comment (markdown)"
+                textarea {
+                    class: "{INPUT_CLASS} min-h-28",
+                    name: "comment",
+                    required: true,
+                    minlength: 2,
+                    maxlength: 5000,
+                    value: "{body}",
+                    oninput: move |evt| body.set(evt.value()),
+                }
+            }
+            div { class: "flex flex-wrap items-center gap-3",
+                button {
+                    r#type: "submit",
+                    disabled: pending(),
+                    class: "rounded bg-accent px-4 py-2 text-xs font-semibold text-accent-fg transition-colors duration-200 hover:bg-accent/90 disabled:opacity-60",
+                    if pending() { "posting..." } else { "post comment" }
+                }
+                match status() {
+                    Some(Ok(())) => rsx! {
+                        span { class: "text-xs text-accent", "Thanks! Your comment will appear once it's approved." }
+                    },
+                    Some(Err(message)) => rsx! {
+                        span { class: "text-xs text-red-500", "{message}" }
+                    },
+                    None => rsx! {},
+                }
+            }
+        }
+    }
+}
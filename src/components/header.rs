@@ -7,6 +7,8 @@ use crate::{
 
 #[component]
 pub fn Component() -> Element {
+    let mut query = use_signal(String::new);
+
     rsx! {
         header { class: "sticky top-0 right-0 left-0 z-50 border-b border-dashed border-border bg-bg/80 backdrop-blur-md",
             div { class: "mx-auto max-w-4xl px-4 py-3 sm:px-6",
@@ -20,11 +22,39 @@ pub fn Component() -> Element {
                     }
 
                     div { class: "flex items-center gap-4 sm:gap-6",
+                        form {
+                            class: "hidden items-center gap-1 text-sm md:flex",
+                            onsubmit: move |evt: FormEvent| {
+                                evt.prevent_default();
+                                navigator().push(Route::Search { q: query().trim().to_string() });
+                                query.set(String::new());
+                            },
+                            span { class: "text-faint", "/" }
+                            input {
+                                r#type: "search",
+                                name: "q",
+                                value: "{query}",
+                                placeholder: "search",
+                                aria_label: "Search posts",
+                                class: "w-28 bg-transparent text-fg placeholder:text-faint focus:outline-none",
+                                oninput: move |evt| query.set(evt.value()),
+                            }
+                        }
+                        Link {
+                            to: Route::Search { q: String::new() },
+                            class: "nav-link text-sm text-muted transition-colors duration-200 hover:text-fg md:hidden",
+                            "search"
+                        }
                         Link {
                             to: Route::Projects {},
                             class: "nav-link text-sm text-muted transition-colors duration-200 hover:text-fg",
                             "projects"
                         }
+                        Link {
+                            to: Route::References { category: None, year: None },
+                            class: "nav-link hidden text-sm text-muted transition-colors duration-200 hover:text-fg sm:block",
+                            "references"
+                        }
                         Link {
                             to: Route::OpenSource {},
                             class: "nav-link text-sm text-muted transition-colors duration-200 hover:text-fg",
@@ -7,13 +7,15 @@ use dioxus_free_icons::{
     Icon,
 };
 
+use crate::app::Route;
+
 #[component]
 pub fn Component() -> Element {
     rsx! {
         div { class: "flex flex-row gap-3 items-center",
-            a {
-                href: "mailto:info@rust-dd.com",
-                aria_label: "Email",
+            Link {
+                to: Route::Contact {},
+                aria_label: "Contact",
                 class: "text-muted transition-colors duration-200 hover:text-accent",
                 Icon { icon: FaSquareEnvelope, width: 18, height: 18, fill: "currentColor" }
             }
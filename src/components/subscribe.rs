@@ -0,0 +1,61 @@
+use dioxus::prelude::*;
+
+use crate::ssr::newsletter::subscribe;
+
+#[component]
+pub fn Component() -> Element {
+    let mut email = use_signal(String::new);
+    let mut status = use_signal(|| None::<Result<(), String>>);
+    let mut pending = use_signal(|| false);
+
+    let submit = move |evt: FormEvent| async move {
+        evt.prevent_default();
+        if pending() {
+            return;
+        }
+        pending.set(true);
+        let result = subscribe(email().trim().to_string()).await;
+        pending.set(false);
+        match result {
+            Ok(()) => {
+                email.set(String::new());
+                status.set(Some(Ok(())));
+            }
+            Err(err) => status.set(Some(Err(err.to_string()))),
+        }
+    };
+
+    rsx! {
+        section { class: "mt-8 rounded-lg border border-dashed border-border bg-surface p-4 sm:p-5",
+            p { class: "text-xs text-faint", "// newsletter" }
+            p { class: "mt-2 text-sm text-muted", "New posts in your inbox. No spam, unsubscribe in one click." }
+            form { class: "mt-3 flex flex-col gap-2 sm:flex-row", onsubmit: submit,
+                input {
+                    r#type: "email",
+                    name: "email",
+                    required: true,
+                    value: "{email}",
+                    placeholder: "you@example.com",
+                    aria_label: "Email address",
+                    class: "min-w-0 flex-1 rounded border border-border bg-bg px-3 py-2 text-sm text-fg placeholder:text-faint focus:border-accent focus:outline-none",
+                    oninput: move |evt| email.set(evt.value()),
+                }
+                button {
+                    r#type: "submit",
+                    disabled: pending(),
+                    class: "rounded bg-accent px-4 py-2 text-xs font-semibold text-accent-fg transition-colors duration-200 hover:bg-accent/90 disabled:opacity-60",
+                    "subscribe"
+                }
+            }
+            match status() {
+                Some(Ok(())) => rsx! {
+                    p { class: "mt-2 text-xs text-accent", "Check your inbox to confirm your subscription." }
+                },
+                Some(Err(message)) => rsx! {
+                    p { class: "mt-2 text-xs text-red-500", "{message}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use dioxus::prelude::*;
+
+const SEGMENT_CLASSES: &[&str] = &[
+    "bg-accent",
+    "bg-accent/70",
+    "bg-accent/40",
+    "bg-muted",
+    "bg-faint",
+    "bg-border",
+];
+
+/// Share of each technology in a stacked bar. `percentages` pairs with
+/// `stack` by position and is scaled to the total, so it needn't add up to
+/// 100.
+#[component]
+pub fn Component(stack: Vec<String>, percentages: Vec<u8>, #[props(default)] legend: bool) -> Element {
+    let total = percentages.iter().map(|&share| u32::from(share)).sum::<u32>().max(1);
+    let segments = stack
+        .into_iter()
+        .zip(percentages)
+        .enumerate()
+        .map(|(index, (name, share))| {
+            let width = f64::from(share) * 100.0 / f64::from(total);
+            (name, share, width, SEGMENT_CLASSES[index % SEGMENT_CLASSES.len()])
+        })
+        .collect::<Vec<_>>();
+
+    rsx! {
+        div {
+            div { class: "flex h-2 w-full overflow-hidden rounded-full bg-surface-2",
+                for (name, share, width, class) in segments.iter() {
+                    span {
+                        key: "{name}",
+                        class: "{class} h-full",
+                        style: "width: {width:.2}%",
+                        title: "{name} {share}%",
+                    }
+                }
+            }
+            if legend {
+                ul { class: "mt-3 flex flex-wrap gap-x-4 gap-y-1 text-xs text-muted",
+                    for (name, share, _, class) in segments.iter() {
+                        li { key: "{name}", class: "flex items-center gap-1.5",
+                            span { class: "{class} inline-block h-2 w-2 rounded-full" }
+                            span { class: "text-fg", "{name}" }
+                            "{share}%"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
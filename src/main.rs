@@ -1,11 +1,21 @@
 #[cfg(feature = "server")]
 #[tokio::main]
 async fn main() {
-    use axum::routing::get;
+    use axum::routing::{get, post};
     use blog::app::App;
+    use blog::ssr::analytics::record_page_view;
     use blog::ssr::app_state::init_db;
+    use blog::ssr::github;
+    use blog::ssr::newsletter::{
+        one_click_unsubscribe_handler, send_new_posts, send_on_publish, send_on_publish_enabled,
+    };
+    use blog::ssr::og_image::og_image_handler;
+    use blog::ssr::publisher;
     use blog::ssr::redirect::redirect_www;
-    use blog::ssr::server_utils::{robots_handler, rss_handler, sitemap_handler};
+    use blog::ssr::server_utils::{
+        atom_handler, author_rss_handler, json_feed_handler, lint_posts, render_all_posts, robots_handler, rss_handler,
+        sitemap_handler, tag_rss_handler,
+    };
     use dotenvy::dotenv;
     use tower_http::compression::predicate::{NotForContentType, SizeAbove};
     use tower_http::compression::{CompressionLayer, Predicate};
@@ -22,16 +32,81 @@ async fn main() {
         tracing::warn!("There is no corresponding .env file");
     }
 
+    // Loads the site config now so a bad `SITE_CONFIG` fails at startup.
+    tracing::info!("serving {}", blog::seo::site().url);
+
     init_db().await;
 
+    match std::env::args().nth(1).as_deref() {
+        Some("render-posts") => {
+            match render_all_posts().await {
+                Ok((rendered, 0)) => tracing::info!("rendered {rendered} posts"),
+                Ok((rendered, failed)) => {
+                    tracing::error!("rendered {rendered} posts, {failed} failed");
+                    std::process::exit(1);
+                }
+                Err(err) => {
+                    tracing::error!("failed to load posts: {err}");
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Some("lint-posts") => {
+            match lint_posts().await {
+                Ok(diagnostics) if diagnostics.is_empty() => tracing::info!("no rendering problems found"),
+                Ok(diagnostics) => {
+                    for (post, diagnostic) in &diagnostics {
+                        println!(
+                            "{post}:{}: {} ({})",
+                            diagnostic.line, diagnostic.message, diagnostic.source
+                        );
+                    }
+                    std::process::exit(1);
+                }
+                Err(err) => {
+                    tracing::error!("failed to lint posts: {err}");
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Some("send-newsletter") => {
+            match send_new_posts().await {
+                Ok((0, _)) => tracing::info!("no new posts to send"),
+                Ok((posts, sent)) => tracing::info!("sent {posts} posts to {sent} subscribers"),
+                Err(err) => {
+                    tracing::error!("failed to send newsletter: {err}");
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    // Subscribed before the publisher starts, so no announcement is missed.
+    if send_on_publish_enabled() {
+        tokio::spawn(send_on_publish(publisher::subscribe()));
+    }
+    tokio::spawn(publisher::run());
+    tokio::spawn(github::run());
+
     let app = dioxus::server::router(App)
         .route("/rss.xml", get(rss_handler))
+        .route("/atom.xml", get(atom_handler))
+        .route("/feed.json", get(json_feed_handler))
+        .route("/tag/{tag}/rss.xml", get(tag_rss_handler))
+        .route("/author/{id}/rss.xml", get(author_rss_handler))
+        .route("/newsletter/unsubscribe/one-click", post(one_click_unsubscribe_handler))
+        .route("/og/{file}", get(og_image_handler))
         .route("/sitemap.xml", get(sitemap_handler))
         .route("/robots.txt", get(robots_handler))
         .layer(
             tower::ServiceBuilder::new()
                 .layer(TraceLayer::new_for_http())
-                .layer(axum::middleware::from_fn(redirect_www)),
+                .layer(axum::middleware::from_fn(redirect_www))
+                .layer(axum::middleware::from_fn(record_page_view)),
         )
         .layer(
             CompressionLayer::new()
@@ -50,7 +125,12 @@ async fn main() {
     let addr = dioxus::cli_config::fullstack_address_or_localhost();
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
     tracing::info!("listening on http://{}", addr);
-    axum::serve(listener, app.into_make_service()).await.unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
 
 #[cfg(not(feature = "server"))]
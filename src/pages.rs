@@ -1,4 +1,13 @@
+pub mod admin;
+pub mod author;
+pub mod contact;
 pub mod home;
+pub mod newsletter;
 pub mod opensource;
 pub mod post;
+pub mod project;
 pub mod projects;
+pub mod reference;
+pub mod references;
+pub mod search;
+pub mod tag;
@@ -0,0 +1,82 @@
+pub mod analytics;
+pub mod comments;
+pub mod editor;
+pub mod login;
+pub mod posts;
+
+use dioxus::prelude::*;
+
+use crate::{
+    app::Route,
+    components::loader,
+    seo,
+    ssr::admin::{admin_logout, admin_session},
+};
+
+#[component]
+pub fn Layout() -> Element {
+    let title = seo::use_site().title("Admin");
+    let mut session = use_server_future(admin_session)?;
+
+    rsx! {
+        document::Title { "{title}" }
+        document::Meta { name: "robots", content: "noindex, nofollow" }
+        document::Meta { name: "googlebot", content: "noindex, nofollow" }
+
+        SuspenseBoundary {
+            fallback: |_| rsx! { loader::Inline { message: "Loading admin...".to_string() } },
+            match session.read().as_ref() {
+                Some(Ok(Some(username))) => rsx! {
+                    div { class: "w-full font-mono",
+                        nav { class: "flex flex-wrap items-center justify-between gap-3 border-b border-dashed border-border pb-3 text-xs text-muted",
+                            div { class: "flex items-center gap-4",
+                                span { class: "text-accent", "admin" }
+                                Link {
+                                    to: Route::AdminPosts {},
+                                    class: "transition-colors duration-200 hover:text-fg",
+                                    "posts"
+                                }
+                                Link {
+                                    to: Route::AdminNewPost {},
+                                    class: "transition-colors duration-200 hover:text-fg",
+                                    "new post"
+                                }
+                                Link {
+                                    to: Route::AdminComments {},
+                                    class: "transition-colors duration-200 hover:text-fg",
+                                    "comments"
+                                }
+                                Link {
+                                    to: Route::AdminAnalytics {},
+                                    class: "transition-colors duration-200 hover:text-fg",
+                                    "analytics"
+                                }
+                            }
+                            div { class: "flex items-center gap-4",
+                                span { class: "text-faint", "user={username}" }
+                                button {
+                                    class: "transition-colors duration-200 hover:text-accent",
+                                    onclick: move |_| async move {
+                                        let _ = admin_logout().await;
+                                        session.restart();
+                                    },
+                                    "logout"
+                                }
+                            }
+                        }
+                        div { class: "mt-6",
+                            Outlet::<Route> {}
+                        }
+                    }
+                },
+                Some(Ok(None)) => rsx! {
+                    login::Component { on_login: move |_| session.restart() }
+                },
+                Some(Err(err)) => rsx! {
+                    div { class: "mt-8 text-red-500", "Failed to load session: {err}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use dioxus::prelude::*;
+
+use crate::{
+    components::loader,
+    ssr::{
+        admin::admin_analytics,
+        types::{DailyCount, RankedCount},
+    },
+};
+
+const RANGES: [usize; 3] = [7, 30, 90];
+
+#[component]
+pub fn Component() -> Element {
+    let mut days = use_signal(|| 30usize);
+    let report = use_server_future(move || async move { admin_analytics(days()).await })?;
+
+    rsx! {
+        div { class: "flex flex-wrap items-center justify-between gap-3",
+            p { class: "text-xs text-faint", "// cookieless, one visitor = one ip + user agent per day" }
+            div { class: "flex items-center gap-3 text-xs text-muted",
+                for range in RANGES {
+                    button {
+                        class: if days() == range { "text-accent" } else { "transition-colors duration-200 hover:text-fg" },
+                        onclick: move |_| days.set(range),
+                        "{range}d"
+                    }
+                }
+            }
+        }
+
+        SuspenseBoundary {
+            fallback: |_| rsx! { loader::Inline { message: "Loading analytics...".to_string() } },
+            if let Some(result) = report.read().as_ref() {
+                match result {
+                    Ok(report) => {
+                        let views = report.views.iter().map(|day| day.count).sum::<usize>();
+                        let visitors = report.visitors.iter().map(|day| day.count).sum::<usize>();
+                        let subscribers = report.feed_subscribers.last().map(|day| day.count).unwrap_or(0);
+
+                        rsx! {
+                            p { class: "mt-4 text-xs text-muted",
+                                "views: "
+                                span { class: "text-fg", "{views}" }
+                                " | visitors: "
+                                span { class: "text-fg", "{visitors}" }
+                                " | feed subscribers today: "
+                                span { class: "text-fg", "{subscribers}" }
+                            }
+                            Chart { label: "views per day", series: report.views.clone() }
+                            Chart { label: "visitors per day", series: report.visitors.clone() }
+                            Chart { label: "feed subscribers", series: report.feed_subscribers.clone() }
+                            div { class: "mt-6 grid gap-6 md:grid-cols-2",
+                                Ranking { label: "top posts", entries: report.top_posts.clone() }
+                                Ranking { label: "top referrers", entries: report.top_referrers.clone() }
+                            }
+                        }
+                    }
+                    Err(err) => rsx! {
+                        div { class: "mt-8 text-red-500", "Failed to load analytics: {err}" }
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn Chart(label: &'static str, series: Vec<DailyCount>) -> Element {
+    let max = series.iter().map(|day| day.count).max().unwrap_or(0).max(1);
+    let first = series.first().map(|day| day.day.clone()).unwrap_or_default();
+    let last = series.last().map(|day| day.day.clone()).unwrap_or_default();
+
+    rsx! {
+        section { class: "mt-6",
+            h2 { class: "text-xs text-faint", "// {label}" }
+            div { class: "mt-2 flex h-32 items-end gap-px rounded-lg border border-border bg-surface p-2",
+                for day in series.iter() {
+                    div {
+                        class: "flex-1 rounded-sm bg-accent/70 transition-colors duration-150 hover:bg-accent",
+                        style: "height: {day.count * 100 / max}%; min-height: 1px;",
+                        title: "{day.day}: {day.count}",
+                    }
+                }
+            }
+            div { class: "mt-1 flex justify-between text-[11px] text-faint",
+                span { "{first}" }
+                span { "max {max}" }
+                span { "{last}" }
+            }
+        }
+    }
+}
+
+#[component]
+fn Ranking(label: &'static str, entries: Vec<RankedCount>) -> Element {
+    rsx! {
+        section {
+            h2 { class: "text-xs text-faint", "// {label}" }
+            div { class: "mt-2 divide-y divide-border rounded-lg border border-border bg-surface",
+                if entries.is_empty() {
+                    p { class: "px-4 py-3 text-xs text-muted", "nothing yet" }
+                }
+                for entry in entries.iter() {
+                    div { class: "flex items-center justify-between gap-4 px-4 py-2 text-sm",
+                        if let Some(path) = &entry.path {
+                            a {
+                                href: "{path}",
+                                class: "truncate text-fg transition-colors duration-200 hover:text-accent",
+                                "{entry.label}"
+                            }
+                        } else {
+                            span { class: "truncate text-fg", "{entry.label}" }
+                        }
+                        span { class: "text-xs text-faint", "{entry.count}" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use dioxus::prelude::*;
+use surrealdb_types::ToSql;
+
+use crate::{
+    components::loader,
+    ssr::admin::{admin_approve_comment, admin_delete_comment, admin_select_pending_comments},
+};
+
+#[component]
+pub fn Component() -> Element {
+    let mut comments = use_server_future(admin_select_pending_comments)?;
+    let mut error = use_signal(|| None::<String>);
+
+    rsx! {
+        SuspenseBoundary {
+            fallback: |_| rsx! { loader::Inline { message: "Loading comments...".to_string() } },
+            if let Some(result) = comments.read().as_ref() {
+                match result {
+                    Ok(items) => rsx! {
+                        p { class: "text-xs text-muted",
+                            "pending: "
+                            span { class: "text-fg", "{items.len()}" }
+                        }
+                        if let Some(message) = error() {
+                            p { class: "mt-2 text-xs text-red-500", "{message}" }
+                        }
+                        if items.is_empty() {
+                            p { class: "mt-4 text-sm text-muted", "Nothing to moderate." }
+                        }
+                        div { class: "mt-4 flex flex-col gap-3",
+                            for comment in items.iter().cloned() {
+                                article {
+                                    key: "{comment.id.key.to_sql()}",
+                                    class: "rounded-lg border border-border bg-surface p-4",
+                                    div { class: "flex flex-wrap items-center justify-between gap-3 text-xs text-muted",
+                                        p {
+                                            span { class: "text-fg", "{comment.author_name}" }
+                                            if let Some(email) = &comment.email {
+                                                " ({email})"
+                                            }
+                                            " · {comment.created_at} · "
+                                            if comment.is_reply { "reply on " } else { "on " }
+                                            a {
+                                                href: format!("/post/{}#comments", comment.post_slug.clone().unwrap_or_default()),
+                                                class: "text-accent hover:underline",
+                                                "{comment.post_title}"
+                                            }
+                                        }
+                                        div { class: "flex items-center gap-3",
+                                            button {
+                                                class: "transition-colors duration-200 hover:text-accent",
+                                                onclick: {
+                                                    let id = comment.id.key.to_sql();
+                                                    move |_| {
+                                                        let id = id.clone();
+                                                        async move {
+                                                            match admin_approve_comment(id).await {
+                                                                Ok(()) => error.set(None),
+                                                                Err(err) => error.set(Some(err.to_string())),
+                                                            }
+                                                            comments.restart();
+                                                        }
+                                                    }
+                                                },
+                                                "approve"
+                                            }
+                                            button {
+                                                class: "transition-colors duration-200 hover:text-red-500",
+                                                onclick: {
+                                                    let id = comment.id.key.to_sql();
+                                                    move |_| {
+                                                        let id = id.clone();
+                                                        async move {
+                                                            match admin_delete_comment(id).await {
+                                                                Ok(()) => error.set(None),
+                                                                Err(err) => error.set(Some(err.to_string())),
+                                                            }
+                                                            comments.restart();
+                                                        }
+                                                    }
+                                                },
+                                                "delete"
+                                            }
+                                        }
+                                    }
+                                    div {
+                                        class: "prose prose-sm mt-2 max-w-none break-words font-sans prose-p:my-2",
+                                        dangerous_inner_html: "{comment.body_html}"
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    Err(err) => rsx! {
+                        div { class: "mt-8 text-red-500", "Failed to load comments: {err}" }
+                    },
+                }
+            }
+        }
+    }
+}
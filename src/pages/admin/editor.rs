@@ -0,0 +1,312 @@
+use chrono::NaiveDateTime;
+use dioxus::prelude::*;
+use surrealdb_types::{Datetime, ToSql};
+
+use crate::{
+    app::Route,
+    components::loader,
+    ssr::{
+        admin::{
+            admin_create_post, admin_delete_post, admin_preview, admin_select_authors, admin_select_post,
+            admin_update_post,
+        },
+        types::{Author, PostInput, RenderedMarkdown},
+    },
+};
+
+/// `datetime-local` inputs carry no timezone; schedules are entered in UTC.
+const PUBLISH_AT_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+/// Waits out a pause in typing before the preview is rendered. The preview
+/// resource restarts whenever the body changes, which drops a pending wait, so
+/// only the last keystroke of a burst reaches the server.
+const PREVIEW_DEBOUNCE_JS: &str = "await new Promise((resolve) => setTimeout(resolve, 300));";
+
+const INPUT_CLASS: &str =
+    "rounded border border-border bg-bg px-3 py-2 text-sm text-fg focus:border-accent focus:outline-none";
+
+#[component]
+pub fn Component(id: Option<String>) -> Element {
+    let authors = use_server_future(admin_select_authors)?;
+    let post = use_server_future(use_reactive!(|(id)| async move {
+        match id {
+            Some(id) => admin_select_post(id).await.map(Some),
+            None => Ok(None),
+        }
+    }))?;
+
+    rsx! {
+        SuspenseBoundary {
+            fallback: |_| rsx! { loader::Inline { message: "Loading editor...".to_string() } },
+            match (post.read().as_ref(), authors.read().as_ref()) {
+                (Some(Ok(post)), Some(Ok(authors))) => {
+                    let (id, initial) = match post {
+                        Some(post) => (
+                            Some(post.id.key.to_sql()),
+                            PostInput {
+                                title: post.title.clone(),
+                                summary: post.summary.clone(),
+                                body: post.body.clone(),
+                                tags: post.tags.clone(),
+                                author: post.author.id.key.to_sql(),
+                                is_published: post.is_published,
+                                show_cta: post.show_cta,
+                                header_image: post.header_image.clone(),
+                                publish_at: post.publish_at,
+                            },
+                        ),
+                        None => (
+                            None,
+                            PostInput {
+                                author: authors.first().map(|author| author.id.key.to_sql()).unwrap_or_default(),
+                                ..PostInput::default()
+                            },
+                        ),
+                    };
+
+                    rsx! {
+                        Form { key: "{id:?}", id, initial, authors: authors.clone() }
+                    }
+                }
+                (Some(Err(err)), _) | (_, Some(Err(err))) => rsx! {
+                    div { class: "mt-8 text-red-500", "Failed to load post: {err}" }
+                },
+                _ => rsx! {},
+            }
+        }
+    }
+}
+
+#[component]
+fn Form(id: Option<String>, initial: PostInput, authors: Vec<Author>) -> Element {
+    let mut title = use_signal(|| initial.title.clone());
+    let mut summary = use_signal(|| initial.summary.clone());
+    let mut body = use_signal(|| initial.body.clone());
+    let mut tags = use_signal(|| initial.tags.join(", "));
+    let mut author = use_signal(|| initial.author.clone());
+    let mut header_image = use_signal(|| initial.header_image.clone().unwrap_or_default());
+    let mut is_published = use_signal(|| initial.is_published);
+    let mut show_cta = use_signal(|| initial.show_cta);
+    let mut publish_at = use_signal(|| {
+        initial
+            .publish_at
+            .map(|publish_at| publish_at.format(PUBLISH_AT_FORMAT).to_string())
+            .unwrap_or_default()
+    });
+    let mut status = use_signal(|| None::<Result<String, String>>);
+
+    let initial_body = initial.body.clone();
+    let preview = use_resource(move || {
+        let initial_body = initial_body.clone();
+        async move {
+            let markdown = body();
+            if markdown.trim().is_empty() {
+                return Ok(RenderedMarkdown::default());
+            }
+            if markdown != initial_body {
+                let _ = document::eval(PREVIEW_DEBOUNCE_JS).await;
+            }
+            admin_preview(markdown).await
+        }
+    });
+
+    let input = move || PostInput {
+        title: title(),
+        summary: summary(),
+        body: body(),
+        tags: tags()
+            .split(',')
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+        author: author(),
+        is_published: is_published(),
+        show_cta: show_cta(),
+        header_image: Some(header_image().trim().to_string()).filter(|image| !image.is_empty()),
+        publish_at: NaiveDateTime::parse_from_str(publish_at().trim(), PUBLISH_AT_FORMAT)
+            .ok()
+            .map(|publish_at| Datetime::from(publish_at.and_utc())),
+    };
+
+    let save_id = id.clone();
+    let save = move |evt: FormEvent| {
+        let id = save_id.clone();
+        async move {
+            evt.prevent_default();
+            let result = match id {
+                Some(id) => admin_update_post(id, input()).await.map(|_| None),
+                None => admin_create_post(input()).await.map(Some),
+            };
+            match result {
+                Ok(Some(new_id)) => {
+                    navigator().replace(Route::AdminEditPost { id: new_id });
+                }
+                Ok(None) => status.set(Some(Ok("saved".to_string()))),
+                Err(err) => status.set(Some(Err(err.to_string()))),
+            }
+        }
+    };
+
+    let delete_id = id.clone();
+    let delete = move |_| {
+        let id = delete_id.clone();
+        async move {
+            let Some(id) = id else {
+                return;
+            };
+            match admin_delete_post(id).await {
+                Ok(()) => {
+                    navigator().replace(Route::AdminPosts {});
+                }
+                Err(err) => status.set(Some(Err(err.to_string()))),
+            }
+        }
+    };
+
+    rsx! {
+        form { class: "flex flex-col gap-4", onsubmit: save,
+            div { class: "flex flex-wrap items-center justify-between gap-3",
+                p { class: "text-xs text-faint",
+                    if id.is_some() { "// edit post" } else { "// new post" }
+                }
+                div { class: "flex items-center gap-3 text-xs",
+                    match status() {
+                        Some(Ok(message)) => rsx! { span { class: "text-accent", "{message}" } },
+                        Some(Err(message)) => rsx! { span { class: "text-red-500", "{message}" } },
+                        None => rsx! {},
+                    }
+                    if id.is_some() {
+                        button {
+                            r#type: "button",
+                            class: "rounded border border-border px-3 py-1.5 text-muted transition-colors duration-200 hover:border-red-500 hover:text-red-500",
+                            onclick: delete,
+                            "delete"
+                        }
+                    }
+                    button {
+                        r#type: "submit",
+                        class: "rounded bg-accent px-3 py-1.5 font-semibold text-accent-fg transition-colors duration-200 hover:bg-accent/90",
+                        "save"
+                    }
+                }
+            }
+
+            div { class: "grid gap-3 sm:grid-cols-2",
+                label { class: "flex flex-col gap-1 text-xs text-muted sm:col-span-2",
+                    "title"
+                    input {
+                        class: INPUT_CLASS,
+                        value: "{title}",
+                        oninput: move |evt| title.set(evt.value()),
+                    }
+                }
+                label { class: "flex flex-col gap-1 text-xs text-muted sm:col-span-2",
+                    "summary"
+                    textarea {
+                        class: INPUT_CLASS,
+                        rows: 2,
+                        value: "{summary}",
+                        oninput: move |evt| summary.set(evt.value()),
+                    }
+                }
+                label { class: "flex flex-col gap-1 text-xs text-muted",
+                    "tags (comma separated)"
+                    input {
+                        class: INPUT_CLASS,
+                        value: "{tags}",
+                        oninput: move |evt| tags.set(evt.value()),
+                    }
+                }
+                label { class: "flex flex-col gap-1 text-xs text-muted",
+                    "author"
+                    select {
+                        class: INPUT_CLASS,
+                        value: "{author}",
+                        onchange: move |evt| author.set(evt.value()),
+                        for item in authors.iter() {
+                            option {
+                                value: item.id.key.to_sql(),
+                                selected: item.id.key.to_sql() == author(),
+                                "{item.name}"
+                            }
+                        }
+                    }
+                }
+                label { class: "flex flex-col gap-1 text-xs text-muted sm:col-span-2",
+                    "header image url"
+                    input {
+                        class: INPUT_CLASS,
+                        value: "{header_image}",
+                        placeholder: "https://...",
+                        oninput: move |evt| header_image.set(evt.value()),
+                    }
+                }
+                label { class: "flex flex-col gap-1 text-xs text-muted sm:col-span-2",
+                    "publish at (UTC, empty = as soon as published is checked)"
+                    input {
+                        r#type: "datetime-local",
+                        class: INPUT_CLASS,
+                        value: "{publish_at}",
+                        oninput: move |evt| publish_at.set(evt.value()),
+                    }
+                }
+                label { class: "flex items-center gap-2 text-xs text-muted",
+                    input {
+                        r#type: "checkbox",
+                        checked: is_published(),
+                        onchange: move |evt| is_published.set(evt.checked()),
+                    }
+                    "published"
+                }
+                label { class: "flex items-center gap-2 text-xs text-muted",
+                    input {
+                        r#type: "checkbox",
+                        checked: show_cta(),
+                        onchange: move |evt| show_cta.set(evt.checked()),
+                    }
+                    "show contact cta"
+                }
+            }
+
+            div { class: "grid gap-4 lg:grid-cols-2",
+                label { class: "flex flex-col gap-1 text-xs text-muted",
+                    "body (markdown)"
+                    textarea {
+                        class: "{INPUT_CLASS} min-h-[60vh] font-mono",
+                        value: "{body}",
+                        oninput: move |evt| body.set(evt.value()),
+                    }
+                }
+                div { class: "flex flex-col gap-1 text-xs text-muted",
+                    "preview"
+                    div { class: "min-h-[60vh] overflow-auto rounded border border-border bg-surface p-4",
+                        match preview.read().as_ref() {
+                            Some(Ok(rendered)) => rsx! {
+                                if !rendered.diagnostics.is_empty() {
+                                    ul { class: "mb-4 flex flex-col gap-1 rounded border border-dashed border-red-500 p-3 text-red-500",
+                                        for diagnostic in rendered.diagnostics.iter() {
+                                            li { class: "break-words",
+                                                span { class: "font-mono", "line {diagnostic.line}: ${diagnostic.source}$" }
+                                                " — {diagnostic.message}"
+                                            }
+                                        }
+                                    }
+                                }
+                                div {
+                                    class: "prose prose-sm max-w-none break-words font-sans",
+                                    dangerous_inner_html: "{rendered.html}"
+                                }
+                            },
+                            Some(Err(err)) => rsx! {
+                                p { class: "text-red-500", "{err}" }
+                            },
+                            None => rsx! {
+                                p { class: "text-faint", "rendering..." }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
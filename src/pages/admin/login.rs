@@ -0,0 +1,66 @@
+use dioxus::prelude::*;
+
+use crate::ssr::admin::admin_login;
+
+#[component]
+pub fn Component(on_login: EventHandler<()>) -> Element {
+    let mut username = use_signal(String::new);
+    let mut password = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut pending = use_signal(|| false);
+
+    rsx! {
+        section { class: "mx-auto w-full max-w-sm pt-16 font-mono",
+            p { class: "text-xs text-faint", "// admin" }
+            h1 { class: "mt-2 text-2xl font-semibold text-fg", "Sign in" }
+
+            form {
+                class: "mt-6 flex flex-col gap-3 rounded-lg border border-border bg-surface p-5",
+                onsubmit: move |evt: FormEvent| async move {
+                    evt.prevent_default();
+                    pending.set(true);
+                    match admin_login(username(), password()).await {
+                        Ok(()) => {
+                            error.set(None);
+                            on_login.call(());
+                        }
+                        Err(err) => error.set(Some(err.to_string())),
+                    }
+                    password.set(String::new());
+                    pending.set(false);
+                },
+                label { class: "flex flex-col gap-1 text-xs text-muted",
+                    "username"
+                    input {
+                        r#type: "text",
+                        name: "username",
+                        autocomplete: "username",
+                        value: "{username}",
+                        class: "rounded border border-border bg-bg px-3 py-2 text-sm text-fg focus:border-accent focus:outline-none",
+                        oninput: move |evt| username.set(evt.value()),
+                    }
+                }
+                label { class: "flex flex-col gap-1 text-xs text-muted",
+                    "password"
+                    input {
+                        r#type: "password",
+                        name: "password",
+                        autocomplete: "current-password",
+                        value: "{password}",
+                        class: "rounded border border-border bg-bg px-3 py-2 text-sm text-fg focus:border-accent focus:outline-none",
+                        oninput: move |evt| password.set(evt.value()),
+                    }
+                }
+                if let Some(error) = error() {
+                    p { class: "text-xs text-red-500", "{error}" }
+                }
+                button {
+                    r#type: "submit",
+                    disabled: pending(),
+                    class: "mt-2 rounded bg-accent px-4 py-2 text-xs font-semibold text-accent-fg transition-colors duration-200 hover:bg-accent/90 disabled:opacity-50",
+                    "sign in"
+                }
+            }
+        }
+    }
+}
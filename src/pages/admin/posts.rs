@@ -0,0 +1,67 @@
+use chrono::Utc;
+use dioxus::prelude::*;
+use surrealdb_types::ToSql;
+
+use crate::{app::Route, components::loader, ssr::admin::admin_select_posts};
+
+#[component]
+pub fn Component() -> Element {
+    let posts = use_server_future(admin_select_posts)?;
+
+    rsx! {
+        SuspenseBoundary {
+            fallback: |_| rsx! { loader::Inline { message: "Loading posts...".to_string() } },
+            if let Some(result) = posts.read().as_ref() {
+                match result {
+                    Ok(items) => {
+                        let drafts = items.iter().filter(|post| !post.is_published).count();
+
+                        rsx! {
+                            div { class: "flex flex-wrap items-center justify-between gap-3",
+                                p { class: "text-xs text-muted",
+                                    "posts: "
+                                    span { class: "text-fg", "{items.len()}" }
+                                    " | drafts: "
+                                    span { class: "text-fg", "{drafts}" }
+                                }
+                                Link {
+                                    to: Route::AdminNewPost {},
+                                    class: "rounded bg-accent px-3 py-1.5 text-xs font-semibold text-accent-fg transition-colors duration-200 hover:bg-accent/90",
+                                    "+ new post"
+                                }
+                            }
+
+                            div { class: "mt-4 rounded-lg border border-border bg-surface",
+                                div { class: "hidden border-b border-dashed border-border px-4 py-2 text-[11px] font-semibold text-faint sm:grid sm:grid-cols-[90px_1fr_70px]",
+                                    span { "status" }
+                                    span { "title" }
+                                    span { class: "text-right", "views" }
+                                }
+                                div { class: "divide-y divide-border",
+                                    for post in items.iter() {
+                                        Link {
+                                            to: Route::AdminEditPost { id: post.id.key.to_sql() },
+                                            class: "block px-4 py-3 no-underline transition-colors duration-150 hover:bg-surface-2 sm:grid sm:grid-cols-[90px_1fr_70px] sm:items-center",
+                                            if post.is_published && post.publish_at.is_some_and(|publish_at| *publish_at > Utc::now()) {
+                                                span { class: "text-xs text-muted", "scheduled" }
+                                            } else if post.is_published {
+                                                span { class: "text-xs text-accent", "published" }
+                                            } else {
+                                                span { class: "text-xs text-faint", "draft" }
+                                            }
+                                            span { class: "block truncate pr-4 text-sm text-fg", "{post.title}" }
+                                            span { class: "text-xs text-faint sm:text-right", "{post.total_views}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    Err(err) => rsx! {
+                        div { class: "mt-8 text-red-500", "Failed to load posts: {err}" }
+                    },
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::{
+    icons::{
+        fa_brands_icons::{FaGithub, FaLinkedin},
+        fa_solid_icons::FaRss,
+    },
+    Icon,
+};
+
+use crate::{app::Route, components::loader, seo, ssr::api::select_author};
+
+#[component]
+pub fn Component(id: String) -> Element {
+    let site = seo::use_site();
+    let author = {
+        let id = id.clone();
+        use_server_future(use_reactive!(|(id)| async move { select_author(id).await }))?
+    };
+
+    let canonical = site.absolute_url(&format!("/author/{id}"));
+    let feed = format!("/author/{id}/rss.xml");
+
+    rsx! {
+        SuspenseBoundary {
+            fallback: |_| rsx! { loader::Inline { message: "Loading author...".to_string() } },
+            div { class: "w-full font-mono",
+                Link {
+                    to: Route::Home {},
+                    class: "inline-flex gap-1 text-xs text-faint transition-colors duration-200 hover:text-accent",
+                    span { "<-" }
+                    span { "back" }
+                }
+
+                if let Some(result) = author.read().as_ref() {
+                    match result {
+                        Ok(page) => {
+                            let author = &page.author;
+                            let title = site.title(&author.name);
+                            let description = author
+                                .bio
+                                .clone()
+                                .unwrap_or_else(|| format!("Posts by {} on {}.", author.name, site.short_name));
+                            let github = author.github_url();
+                            let twitter = author.twitter_url();
+                            let linkedin = author.linkedin_url();
+                            let structured_data = seo::json_ld(vec![
+                                site.person_ld(author),
+                                site.breadcrumb_ld(vec![(author.name.clone(), format!("/author/{id}"))]),
+                            ]);
+
+                            rsx! {
+                                document::Title { "{title}" }
+                                document::Meta { name: "description", content: "{description}" }
+                                document::Meta { name: "robots", content: "index, follow" }
+                                document::Meta { name: "googlebot", content: "index, follow" }
+                                document::Meta { property: "og:type", content: "profile" }
+                                document::Meta { property: "og:title", content: "{title}" }
+                                document::Meta { property: "og:description", content: "{description}" }
+                                document::Meta { property: "og:url", content: "{canonical}" }
+                                document::Meta { property: "og:image", content: "{site.default_og_image}" }
+                                document::Meta { name: "twitter:card", content: "summary" }
+                                document::Meta { name: "twitter:title", content: "{title}" }
+                                document::Meta { name: "twitter:description", content: "{description}" }
+                                document::Meta { name: "twitter:url", content: "{canonical}" }
+                                document::Link { rel: "canonical", href: "{canonical}" }
+                                document::Link {
+                                    rel: "alternate",
+                                    r#type: "application/rss+xml",
+                                    title: "{site.short_name} · {author.name} RSS Feed",
+                                    href: site.absolute_url(&feed)
+                                }
+                                document::Script { r#type: "application/ld+json", "{structured_data}" }
+
+                                section { class: "animate-rise py-4",
+                                    p { class: "text-xs text-faint", "// author" }
+                                    h1 { class: "mt-2 text-3xl font-semibold leading-tight text-fg sm:text-4xl md:text-5xl",
+                                        "{author.name}"
+                                    }
+                                    if let Some(bio) = &author.bio {
+                                        p { class: "mt-3 max-w-2xl text-sm leading-relaxed text-muted", "{bio}" }
+                                    }
+                                    div { class: "mt-4 flex flex-row items-center gap-3",
+                                        if let Some(github) = github {
+                                            a {
+                                                href: "{github}",
+                                                rel: "noopener noreferrer me",
+                                                target: "_blank",
+                                                aria_label: "GitHub",
+                                                class: "text-muted transition-colors duration-200 hover:text-accent",
+                                                Icon { icon: FaGithub, width: 16, height: 16, fill: "currentColor" }
+                                            }
+                                        }
+                                        if let Some(twitter) = twitter {
+                                            a {
+                                                href: "{twitter}",
+                                                rel: "noopener noreferrer me",
+                                                target: "_blank",
+                                                aria_label: "X",
+                                                class: "text-muted transition-colors duration-200 hover:text-accent",
+                                                svg {
+                                                    view_box: "0 0 512 512",
+                                                    fill: "currentColor",
+                                                    width: "16",
+                                                    height: "16",
+                                                    path { d: "M389.2 48h70.6L305.6 224.2 487 464H345L233.7 318.6 106.5 464H35.8L200.7 275.5 26.8 48h145.6l100.5 132.9L389.2 48zm-24.8 373.8h39.1L154.4 88h-42l252 333.8z" }
+                                                }
+                                            }
+                                        }
+                                        if let Some(linkedin) = linkedin {
+                                            a {
+                                                href: "{linkedin}",
+                                                rel: "noopener noreferrer me",
+                                                target: "_blank",
+                                                aria_label: "LinkedIn",
+                                                class: "text-muted transition-colors duration-200 hover:text-accent",
+                                                Icon { icon: FaLinkedin, width: 16, height: 16, fill: "currentColor" }
+                                            }
+                                        }
+                                        a {
+                                            href: "{feed}",
+                                            rel: "noopener noreferrer",
+                                            target: "_blank",
+                                            aria_label: "RSS",
+                                            class: "text-muted transition-colors duration-200 hover:text-accent",
+                                            Icon { icon: FaRss, width: 16, height: 16, fill: "currentColor" }
+                                        }
+                                    }
+                                }
+
+                                div { class: "mt-4 border-y border-dashed border-border py-3 text-xs text-muted",
+                                    span { "posts: " span { class: "text-fg", "{page.posts.len()}" } }
+                                }
+
+                                if !page.posts.is_empty() {
+                                    section { class: "mt-6",
+                                        div { class: "rounded-lg border border-border bg-surface",
+                                            div { class: "divide-y divide-border",
+                                                for post in page.posts.iter() {
+                                                    Link {
+                                                        to: Route::Post { slug: post.slug.clone().unwrap_or_default() },
+                                                        class: "block px-4 py-3 no-underline transition-colors duration-150 hover:bg-surface-2",
+                                                        p { class: "text-sm text-fg", "{post.title}" }
+                                                        p { class: "mt-1 text-sm leading-relaxed text-muted", "{post.summary}" }
+                                                        p { class: "mt-1 text-xs text-faint",
+                                                            "{post.created_at} · {post.read_time}min · {post.total_views} views"
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        Err(err) => rsx! {
+                            div { class: "mt-8 text-red-500", "Failed to load author: {err}" }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use dioxus::prelude::*;
+
+use crate::{
+    seo,
+    ssr::{contact::submit_contact, types::ContactInput},
+};
+
+const INPUT_CLASS: &str = "rounded border border-border bg-bg px-3 py-2 text-sm text-fg placeholder:text-faint focus:border-accent focus:outline-none";
+
+#[component]
+pub fn Component() -> Element {
+    let site = seo::use_site();
+    let mut name = use_signal(String::new);
+    let mut email = use_signal(String::new);
+    let mut message = use_signal(String::new);
+    let mut website = use_signal(String::new);
+    let mut status = use_signal(|| None::<Result<(), String>>);
+    let mut pending = use_signal(|| false);
+
+    let title = site.title("Contact");
+    let description = "Tell us about your Rust project. We build and consult on production Rust systems.";
+    let canonical = site.absolute_url("/contact");
+
+    let submit = move |evt: FormEvent| async move {
+        evt.prevent_default();
+        if pending() {
+            return;
+        }
+        pending.set(true);
+        let result = submit_contact(ContactInput {
+            name: name(),
+            email: email(),
+            message: message(),
+            website: website(),
+        })
+        .await;
+        pending.set(false);
+        match result {
+            Ok(()) => {
+                name.set(String::new());
+                email.set(String::new());
+                message.set(String::new());
+                status.set(Some(Ok(())));
+            }
+            Err(err) => status.set(Some(Err(err.to_string()))),
+        }
+    };
+
+    rsx! {
+        document::Title { "{title}" }
+        document::Meta { name: "description", content: "{description}" }
+        document::Meta { name: "robots", content: "index, follow" }
+        document::Meta { name: "googlebot", content: "index, follow" }
+        document::Meta { property: "og:type", content: "website" }
+        document::Meta { property: "og:title", content: "{title}" }
+        document::Meta { property: "og:description", content: "{description}" }
+        document::Meta { property: "og:url", content: "{canonical}" }
+        document::Meta { property: "og:image", content: "{site.default_og_image}" }
+        document::Meta { name: "twitter:card", content: "summary_large_image" }
+        document::Meta { name: "twitter:title", content: "{title}" }
+        document::Meta { name: "twitter:description", content: "{description}" }
+        document::Meta { name: "twitter:url", content: "{canonical}" }
+        document::Meta { name: "twitter:image", content: "{site.default_og_image}" }
+        document::Link { rel: "canonical", href: "{canonical}" }
+
+        div { class: "w-full font-mono",
+            section { class: "animate-rise py-4",
+                p { class: "text-xs text-faint", "// contact" }
+                h1 { class: "mt-2 text-3xl font-semibold leading-tight text-fg sm:text-4xl md:text-5xl",
+                    "Need Rust expertise?"
+                }
+                p { class: "mt-3 max-w-2xl text-sm leading-relaxed text-muted",
+                    "Tell us what you're building. We usually reply within two business days."
+                }
+            }
+
+            form {
+                class: "mt-4 flex flex-col gap-3 rounded-lg border border-border bg-surface p-4 sm:p-6",
+                onsubmit: submit,
+                div { class: "grid gap-3 sm:grid-cols-2",
+                    label { class: "flex flex-col gap-1 text-xs text-muted",
+                        "name"
+                        input {
+                            class: INPUT_CLASS,
+                            name: "name",
+                            required: true,
+                            maxlength: 100,
+                            autocomplete: "name",
+                            value: "{name}",
+                            oninput: move |evt| name.set(evt.value()),
+                        }
+                    }
+                    label { class: "flex flex-col gap-1 text-xs text-muted",
+                        "email"
+                        input {
+                            class: INPUT_CLASS,
+                            r#type: "email",
+                            name: "email",
+                            required: true,
+                            autocomplete: "email",
+                            value: "{email}",
+                            oninput: move |evt| email.set(evt.value()),
+                        }
+                    }
+                }
+                // Honeypot: hidden from people and assistive tech, filled in by bots.
+                label { class: "hidden", aria_hidden: "true",
+                    "website"
+                    input {
+                        name: "website",
+                        tabindex: "-1",
+                        autocomplete: "off",
+                        value: "{website}",
+                        oninput: move |evt| website.set(evt.value()),
+                    }
+                }
+                label { class: "flex flex-col gap-1 text-xs text-muted",
+                    "message"
+                    textarea {
+                        class: "{INPUT_CLASS} min-h-40",
+                        name: "message",
+                        required: true,
+                        minlength: 10,
+                        maxlength: 5000,
+                        placeholder: "project, timeline, team size...",
+                        value: "{message}",
+                        oninput: move |evt| message.set(evt.value()),
+                    }
+                }
+                div { class: "flex flex-wrap items-center gap-3",
+                    button {
+                        r#type: "submit",
+                        disabled: pending(),
+                        class: "rounded bg-accent px-4 py-2 text-xs font-semibold text-accent-fg transition-colors duration-200 hover:bg-accent/90 disabled:opacity-60",
+                        if pending() { "sending..." } else { "send message" }
+                    }
+                    match status() {
+                        Some(Ok(())) => rsx! {
+                            span { class: "text-xs text-accent", "Thanks! We'll get back to you soon." }
+                        },
+                        Some(Err(message)) => rsx! {
+                            span { class: "text-xs text-red-500", "{message}" }
+                        },
+                        None => rsx! {},
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,28 +1,34 @@
 use dioxus::prelude::*;
 use std::collections::BTreeMap;
 
-use crate::{app::Route, components::loader, seo, ssr::api::select_posts};
+use crate::{
+    app::Route,
+    components::{loader, subscribe},
+    seo,
+    ssr::api::select_posts,
+};
 
 #[component]
 pub fn Component() -> Element {
+    let site = seo::use_site();
     let posts = use_server_future(select_posts)?;
-    let canonical = seo::absolute_url("/");
+    let canonical = site.absolute_url("/");
 
     rsx! {
-        document::Title { "{seo::SITE_NAME}" }
-        document::Meta { name: "description", content: seo::SITE_DESCRIPTION }
+        document::Title { "{site.name}" }
+        document::Meta { name: "description", content: "{site.description}" }
         document::Meta { name: "robots", content: "index, follow" }
         document::Meta { name: "googlebot", content: "index, follow" }
         document::Meta { property: "og:type", content: "website" }
-        document::Meta { property: "og:title", content: seo::SITE_NAME }
-        document::Meta { property: "og:description", content: seo::SITE_DESCRIPTION }
+        document::Meta { property: "og:title", content: "{site.name}" }
+        document::Meta { property: "og:description", content: "{site.description}" }
         document::Meta { property: "og:url", content: "{canonical}" }
-        document::Meta { property: "og:image", content: seo::DEFAULT_OG_IMAGE }
+        document::Meta { property: "og:image", content: "{site.default_og_image}" }
         document::Meta { name: "twitter:card", content: "summary_large_image" }
-        document::Meta { name: "twitter:title", content: seo::SITE_NAME }
-        document::Meta { name: "twitter:description", content: seo::SITE_DESCRIPTION }
+        document::Meta { name: "twitter:title", content: "{site.name}" }
+        document::Meta { name: "twitter:description", content: "{site.description}" }
         document::Meta { name: "twitter:url", content: "{canonical}" }
-        document::Meta { name: "twitter:image", content: seo::DEFAULT_OG_IMAGE }
+        document::Meta { name: "twitter:image", content: "{site.default_og_image}" }
         document::Link { rel: "canonical", href: "{canonical}" }
 
         SuspenseBoundary {
@@ -87,7 +93,16 @@ pub fn Component() -> Element {
                                         span { class: "text-faint", "::" }
                                         span { class: "text-faint", "{{" }
                                         span { class: "text-fg",
-                                            {tag_names.join(", ")}
+                                            for (index, name) in tag_names.iter().enumerate() {
+                                                if index > 0 {
+                                                    ", "
+                                                }
+                                                Link {
+                                                    to: Route::Tag { tag: name.clone(), page: None },
+                                                    class: "transition-colors duration-200 hover:text-accent",
+                                                    "{name}"
+                                                }
+                                            }
                                         }
                                         span { class: "text-faint", "}};" }
                                     }
@@ -154,6 +169,8 @@ pub fn Component() -> Element {
                         },
                     }
                 }
+
+                subscribe::Component {}
             }
         }
     }
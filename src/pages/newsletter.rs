@@ -0,0 +1,65 @@
+use dioxus::prelude::*;
+
+use crate::{
+    app::Route,
+    seo,
+    ssr::newsletter::{confirm_subscription, unsubscribe},
+};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Action {
+    Confirm,
+    Unsubscribe,
+}
+
+/// Landing page for the links in newsletter emails. The token is only
+/// submitted from the browser, never during SSR, so mail scanners that
+/// prefetch links can't confirm or cancel a subscription.
+#[component]
+pub fn Component(action: Action, token: String) -> Element {
+    let site = seo::use_site();
+    let mut result = use_signal(|| None::<Result<(), String>>);
+
+    use_effect(use_reactive!(|(action, token)| {
+        spawn(async move {
+            let response = match action {
+                Action::Confirm => confirm_subscription(token).await,
+                Action::Unsubscribe => unsubscribe(token).await,
+            };
+            result.set(Some(response.map_err(|err| err.to_string())));
+        });
+    }));
+
+    let (title, pending, done) = match action {
+        Action::Confirm => (
+            "Confirm subscription",
+            "Confirming your subscription...",
+            "You're subscribed. New posts will land in your inbox.",
+        ),
+        Action::Unsubscribe => (
+            "Unsubscribe",
+            "Unsubscribing...",
+            "You're unsubscribed and won't receive any more emails.",
+        ),
+    };
+
+    rsx! {
+        document::Title { "{site.title(title)}" }
+        document::Meta { name: "robots", content: "noindex, nofollow" }
+
+        section { class: "mx-auto max-w-3xl pt-16 text-center font-mono",
+            p { class: "text-xs text-faint", "// newsletter" }
+            h1 { class: "mt-2 text-3xl font-semibold text-fg", "{title}" }
+            match result() {
+                None => rsx! { p { class: "mt-4 text-muted", "{pending}" } },
+                Some(Ok(())) => rsx! { p { class: "mt-4 text-muted", "{done}" } },
+                Some(Err(message)) => rsx! { p { class: "mt-4 text-red-500", "{message}" } },
+            }
+            Link {
+                to: Route::Home {},
+                class: "inline-flex mt-8 text-accent hover:underline",
+                "Go back home"
+            }
+        }
+    }
+}
@@ -2,167 +2,26 @@ use std::collections::BTreeMap;
 
 use dioxus::prelude::*;
 
-use crate::{components::loader, seo, ssr::api::select_repo_stars};
-
-pub(crate) struct OssProject {
-    pub(crate) name: &'static str,
-    pub(crate) description: &'static str,
-    pub(crate) url: &'static str,
-    pub(crate) github_repo: &'static str,
-    pub(crate) stars: u32,
-    pub(crate) language: &'static str,
-    pub(crate) topics: &'static [&'static str],
-}
-
-pub(crate) const PROJECTS: &[OssProject] = &[
-    OssProject {
-        name: "tako",
-        description: "Tako is a lightweight and minimalistic web framework built on Tokio and Hyper written in Rust.",
-        url: "https://github.com/rust-dd/tako",
-        github_repo: "rust-dd/tako",
-        stars: 146,
-        language: "Rust",
-        topics: &["async", "hyper", "rust", "tokio", "webframework"],
-    },
-    OssProject {
-        name: "stochastic-rs",
-        description: "A Rust library designed for high-performance simulation and analysis of stochastic processes and models in quantitative finance.",
-        url: "https://github.com/rust-dd/stochastic-rs",
-        github_repo: "rust-dd/stochastic-rs",
-        stars: 141,
-        language: "Rust",
-        topics: &["quant", "finance", "stochastic", "simulation", "statistics"],
-    },
-    OssProject {
-        name: "rust-axum-async-graphql-postgres-redis-starter",
-        description: "Starter template using Rust with Axum, Async-GraphQL, PostgreSQL, and Redis for building high-performance web APIs.",
-        url: "https://github.com/rust-dd/rust-axum-async-graphql-postgres-redis-starter",
-        github_repo: "rust-dd/rust-axum-async-graphql-postgres-redis-starter",
-        stars: 42,
-        language: "Rust",
-        topics: &["axum", "graphql", "postgres", "redis"],
-    },
-    OssProject {
-        name: "rsql",
-        description: "Fast PostgreSQL client built with Rust, Tauri, and React for querying data, running EXPLAIN, and exploring large result sets.",
-        url: "https://rsql.rust-dd.com/#demo",
-        github_repo: "rust-dd/rust-sql",
-        stars: 37,
-        language: "TypeScript",
-        topics: &["postgresql", "rust", "tauri", "react"],
-    },
-    OssProject {
-        name: "embedded-dht-rs",
-        description: "A Rust library that provides full support for DHT11, DHT22, and DHT20 (AHT20) temperature and humidity sensors.",
-        url: "https://github.com/rust-dd/embedded-dht-rs",
-        github_repo: "rust-dd/embedded-dht-rs",
-        stars: 34,
-        language: "Rust",
-        topics: &["dht11", "dht22", "esp32", "embedded"],
-    },
-    OssProject {
-        name: "aoc-2024",
-        description: "Solving the Advent of Code 2024 puzzles using the Rust programming language.",
-        url: "https://github.com/rust-dd/aoc-2024",
-        github_repo: "rust-dd/aoc-2024",
-        stars: 24,
-        language: "Rust",
-        topics: &["advent-of-code", "rust"],
-    },
-    OssProject {
-        name: "iTransformer",
-        description: "An iTransformer implementation in Rust for time-series forecasting.",
-        url: "https://github.com/rust-dd/iTransformer",
-        github_repo: "rust-dd/iTransformer",
-        stars: 18,
-        language: "Rust",
-        topics: &["ai", "transformers", "mathematics"],
-    },
-    OssProject {
-        name: "blog",
-        description: "Blog engine written in Rust, powered by Dioxus and SurrealDB.",
-        url: "https://github.com/rust-dd/blog",
-        github_repo: "rust-dd/blog",
-        stars: 16,
-        language: "Rust",
-        topics: &["blog", "dioxus", "surrealdb"],
+use crate::{
+    components::loader,
+    seo,
+    ssr::{
+        api::{select_oss_projects, select_repo_stars},
+        types::{DailyCount, OssProject, RepoStats},
     },
-    OssProject {
-        name: "google-calendar-cli",
-        description: "Google Calendar CLI written in Rust.",
-        url: "https://github.com/rust-dd/google-calendar-cli",
-        github_repo: "rust-dd/google-calendar-cli",
-        stars: 13,
-        language: "Rust",
-        topics: &["cli", "google-calendar", "rust"],
-    },
-    OssProject {
-        name: "probability-benchmark",
-        description: "Scientific computing benchmark: Rust vs Zig vs C.",
-        url: "https://github.com/rust-dd/probability-benchmark",
-        github_repo: "rust-dd/probability-benchmark",
-        stars: 11,
-        language: "Zig",
-        topics: &["rust", "zig", "c", "stochastic-processes"],
-    },
-    OssProject {
-        name: "tryrust.org",
-        description: "An interactive Rust tutorial in the browser.",
-        url: "https://github.com/rust-dd/tryrust.org",
-        github_repo: "rust-dd/tryrust.org",
-        stars: 8,
-        language: "Rust",
-        topics: &["axum", "leptos", "tutorial"],
-    },
-    OssProject {
-        name: "async-safe-defer",
-        description: "Minimal async- and sync-capable defer crate for Rust.",
-        url: "https://github.com/rust-dd/async-safe-defer",
-        github_repo: "rust-dd/async-safe-defer",
-        stars: 7,
-        language: "Rust",
-        topics: &["async", "defer", "embedded"],
-    },
-    OssProject {
-        name: "react-native-qdrant-edge",
-        description: "Embedded vector search for React Native powered by Qdrant Edge, running fully offline on-device.",
-        url: "https://github.com/rust-dd/react-native-qdrant-edge",
-        github_repo: "rust-dd/react-native-qdrant-edge",
-        stars: 0,
-        language: "TypeScript",
-        topics: &["react-native", "qdrant", "vector-search", "embedded"],
-    },
-    OssProject {
-        name: "react-native-scc",
-        description: "Rust-powered, ultra-fast persistent key-value storage for React Native and Expo — a lock-free hash map behind Nitro Modules, built as a drop-in MMKV alternative.",
-        url: "https://github.com/rust-dd/react-native-scc",
-        github_repo: "rust-dd/react-native-scc",
-        stars: 2,
-        language: "Rust",
-        topics: &["react-native", "rust", "nitro-modules", "key-value-store", "mmkv-alternative"],
-    },
-    OssProject {
-        name: "ito",
-        description: "Terminal UI to browse, configure, and plot every stochastic process in stochastic-rs — Monte-Carlo paths on the CPU, in f64.",
-        url: "https://github.com/rust-dd/ito",
-        github_repo: "rust-dd/ito",
-        stars: 2,
-        language: "Rust",
-        topics: &["rust", "tui", "stochastic", "quant"],
-    },
-];
+};
 
 #[component]
 pub fn Component() -> Element {
+    let site = seo::use_site();
+    let projects = use_server_future(select_oss_projects)?;
     let stars = use_server_future(select_repo_stars)?;
-    let title = "Open Source | Rust-DD";
-    let description =
-        "Open source projects by Rust-DD — web frameworks, quant finance, embedded systems, and developer tools.";
-    let canonical = seo::absolute_url("/opensource");
-
-    let mut languages: Vec<&str> = PROJECTS.iter().map(|p| p.language).collect();
-    languages.sort();
-    languages.dedup();
+    let title = site.title("Open Source");
+    let description = format!(
+        "Open source projects by {} — web frameworks, quant finance, embedded systems, and developer tools.",
+        site.short_name
+    );
+    let canonical = site.absolute_url("/opensource");
 
     rsx! {
         document::Title { "{title}" }
@@ -173,30 +32,33 @@ pub fn Component() -> Element {
         document::Meta { property: "og:title", content: "{title}" }
         document::Meta { property: "og:description", content: "{description}" }
         document::Meta { property: "og:url", content: "{canonical}" }
-        document::Meta { property: "og:image", content: seo::DEFAULT_OG_IMAGE }
+        document::Meta { property: "og:image", content: "{site.default_og_image}" }
         document::Meta { name: "twitter:card", content: "summary_large_image" }
         document::Meta { name: "twitter:title", content: "{title}" }
         document::Meta { name: "twitter:description", content: "{description}" }
         document::Meta { name: "twitter:url", content: "{canonical}" }
-        document::Meta { name: "twitter:image", content: seo::DEFAULT_OG_IMAGE }
+        document::Meta { name: "twitter:image", content: "{site.default_og_image}" }
         document::Link { rel: "canonical", href: "{canonical}" }
 
         SuspenseBoundary {
             fallback: |_| rsx! { loader::Inline { message: "Loading projects...".to_string() } },
-            match stars.read().as_ref() {
-                Some(result) => {
+            match (projects.read().as_ref(), stars.read().as_ref()) {
+                (Some(Err(err)), _) => rsx! {
+                    div { class: "mt-8 text-red-500", "Failed to load projects: {err}" }
+                },
+                (Some(Ok(projects)), Some(result)) => {
                     let repo_stars = result.as_ref().ok();
-                    let total_stars: u32 = PROJECTS
+                    let total_stars: u32 = projects
                         .iter()
                         .map(|project| project_stars(project, repo_stars))
                         .sum();
+                    let gained_this_month: i64 = repo_stars
+                        .map(|stats| stats.values().map(|stats| stats.gained_this_month).sum())
+                        .unwrap_or(0);
 
-                    let mut sorted_projects: Vec<&OssProject> = PROJECTS.iter().collect();
-                    sorted_projects.sort_by(|a, b| {
-                        project_stars(b, repo_stars)
-                            .cmp(&project_stars(a, repo_stars))
-                            .then_with(|| a.name.cmp(b.name))
-                    });
+                    let mut languages: Vec<&str> = projects.iter().map(|p| p.language.as_str()).collect();
+                    languages.sort();
+                    languages.dedup();
 
                     rsx! {
                         div { class: "w-full font-mono",
@@ -214,7 +76,7 @@ pub fn Component() -> Element {
                                 div { class: "flex flex-wrap gap-x-4 gap-y-1",
                                     span {
                                         "repos: "
-                                        span { class: "text-fg", "{PROJECTS.len()}" }
+                                        span { class: "text-fg", "{projects.len()}" }
                                     }
                                     span { class: "hidden sm:inline", "|" }
                                     span {
@@ -222,6 +84,11 @@ pub fn Component() -> Element {
                                         span { class: "text-fg", "{total_stars}" }
                                     }
                                     span { class: "hidden sm:inline", "|" }
+                                    span {
+                                        "this month: "
+                                        span { class: "text-fg", "{signed(gained_this_month)}" }
+                                    }
+                                    span { class: "hidden sm:inline", "|" }
                                     span {
                                         "org: "
                                         a {
@@ -247,7 +114,7 @@ pub fn Component() -> Element {
                             }
 
                             section { class: "mt-6 grid gap-4 md:gap-5 lg:grid-cols-2",
-                                for (index, project) in sorted_projects.into_iter().enumerate() {
+                                for (index, project) in projects.iter().enumerate() {
                                     a {
                                         href: "{project.url}",
                                         target: "_blank",
@@ -297,6 +164,19 @@ pub fn Component() -> Element {
                                             }
                                         }
 
+                                        if let Some(stats) = repo_stars.and_then(|stats| stats.get(&project.github_repo)) {
+                                            p { class: "mt-2 text-xs text-muted", "{repo_details(stats)}" }
+                                            if stats.star_history.len() > 1 {
+                                                div { class: "mt-3 flex items-end gap-3",
+                                                    Sparkline { points: stats.star_history.clone() }
+                                                    span { class: "shrink-0 text-xs text-faint",
+                                                        span { class: "text-fg", "{signed(stats.gained_this_month)}" }
+                                                        " this month"
+                                                    }
+                                                }
+                                            }
+                                        }
+
                                         div { class: "mt-3 inline-flex items-center gap-1 text-xs text-muted transition-colors duration-200 group-hover:text-accent",
                                             "open project"
                                             span { ">" }
@@ -307,15 +187,89 @@ pub fn Component() -> Element {
                         }
                     }
                 }
-                None => rsx! {},
+                _ => rsx! {},
             }
         }
     }
 }
 
-fn project_stars(project: &OssProject, repo_stars: Option<&BTreeMap<String, u32>>) -> u32 {
+fn project_stars(project: &OssProject, repo_stars: Option<&BTreeMap<String, RepoStats>>) -> u32 {
     repo_stars
-        .and_then(|stars| stars.get(project.github_repo))
-        .copied()
-        .unwrap_or(project.stars)
+        .and_then(|stats| stats.get(&project.github_repo))
+        .map_or(project.stars, |stats| stats.stars)
+}
+
+fn signed(count: i64) -> String {
+    if count > 0 {
+        format!("+{count}")
+    } else {
+        count.to_string()
+    }
+}
+
+/// Stars over time as a line scaled to the lowest and highest count, so
+/// small repos still show their trend.
+#[component]
+fn Sparkline(points: Vec<DailyCount>) -> Element {
+    let min = points.iter().map(|point| point.count).min().unwrap_or(0);
+    let max = points.iter().map(|point| point.count).max().unwrap_or(0);
+    let last = points.len().saturating_sub(1).max(1);
+    let line = points
+        .iter()
+        .enumerate()
+        .map(|(index, point)| {
+            let x = index as f64 * 100.0 / last as f64;
+            let y = if max == min {
+                12.0
+            } else {
+                23.0 - (point.count - min) as f64 * 22.0 / (max - min) as f64
+            };
+            format!("{x:.2},{y:.2}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let label = match (points.first(), points.last()) {
+        (Some(first), Some(latest)) => format!("{} to {} stars since {}", first.count, latest.count, first.day),
+        _ => String::new(),
+    };
+
+    rsx! {
+        svg {
+            class: "h-6 w-full min-w-0 text-accent",
+            view_box: "0 0 100 24",
+            preserve_aspect_ratio: "none",
+            role: "img",
+            "aria-label": "{label}",
+            polyline {
+                points: "{line}",
+                fill: "none",
+                stroke: "currentColor",
+                stroke_width: "1.5",
+                stroke_linejoin: "round",
+                vector_effect: "non-scaling-stroke",
+            }
+        }
+    }
+}
+
+/// `3 forks · 5 issues · MIT · v1.2.0 · pushed Jan 2, 2025`, skipping what
+/// the repo doesn't have.
+fn repo_details(stats: &RepoStats) -> String {
+    let pushed = stats.pushed_at.as_deref().and_then(|pushed_at| {
+        chrono::DateTime::parse_from_rfc3339(pushed_at)
+            .ok()
+            .map(|date| format!("pushed {}", date.format("%b %-d, %Y")))
+    });
+
+    [
+        Some(format!("{} forks", stats.forks)),
+        Some(format!("{} issues", stats.open_issues)),
+        stats.license.clone(),
+        stats.latest_release.clone(),
+        pushed,
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" · ")
 }
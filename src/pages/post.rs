@@ -1,24 +1,62 @@
 use dioxus::prelude::*;
+use surrealdb_types::ToSql;
 
 use crate::{
     app::Route,
-    components::loader,
+    components::{comments, loader, subscribe},
     seo,
-    ssr::api::{increment_views, select_post},
+    ssr::{
+        api::{increment_views, select_post, select_related_posts},
+        types::TocEntry,
+    },
 };
 
+const RELATED_POSTS_LIMIT: usize = 3;
+
+/// Receives the post's heading ids, then marks the heading nearest the top of
+/// the viewport as the one being read and reports its id whenever that
+/// changes. Starting it again, or running `TOC_SPY_STOP_JS`, removes the
+/// previous listener.
+const TOC_SPY_JS: &str = r#"
+window.tocSpy?.abort();
+const spy = new AbortController();
+window.tocSpy = spy;
+const anchors = await dioxus.recv();
+let current = null;
+const update = () => {
+    const headings = anchors.map((id) => document.getElementById(id)).filter(Boolean);
+    let active = headings[0];
+    for (const heading of headings) {
+        if (heading.getBoundingClientRect().top > 96) break;
+        active = heading;
+    }
+    if (active && active.id !== current) {
+        current = active.id;
+        dioxus.send(current);
+    }
+};
+update();
+window.addEventListener("scroll", update, { passive: true, signal: spy.signal });
+await new Promise((resolve) => spy.signal.addEventListener("abort", resolve));
+"#;
+
+const TOC_SPY_STOP_JS: &str = "window.tocSpy?.abort();";
+
 #[component]
 pub fn Component(slug: String) -> Element {
-    let post = use_server_future(move || {
-        let slug = slug.clone();
-        async move { select_post(slug).await }
-    })?;
-    let mut view_counted = use_signal(|| false);
+    let site = seo::use_site();
+    let post = use_server_future(use_reactive!(|(slug)| async move { select_post(slug).await }))?;
+    // The component is reused when a link leads to another post, so count
+    // each slug once rather than once per mount.
+    let mut counted_slug = use_signal(|| None::<String>);
 
     use_effect(move || {
-        if cfg!(not(debug_assertions)) && !*view_counted.read() {
+        if cfg!(not(debug_assertions)) {
             if let Some(Ok(post)) = post.read().as_ref() {
-                view_counted.set(true);
+                if *counted_slug.peek() == post.slug {
+                    return;
+                }
+                counted_slug.set(post.slug.clone());
                 let id = surrealdb_types::ToSql::to_sql(&post.id.key);
                 spawn(async move {
                     let _ = increment_views(id).await;
@@ -33,14 +71,22 @@ pub fn Component(slug: String) -> Element {
             if let Some(result) = post.read().as_ref() {
                 match result {
                     Ok(post) => {
-                        let canonical = seo::absolute_url(&format!(
+                        let canonical = site.absolute_url(&format!(
                             "/post/{}",
                             post.slug.clone().unwrap_or_default()
                         ));
-                        let og_image = post
-                            .header_image
-                            .clone()
-                            .unwrap_or_else(|| seo::DEFAULT_OG_IMAGE.to_string());
+                        let og_image = site.post_og_image(post);
+                        let crumbs = post
+                            .tags
+                            .first()
+                            .map(|tag| {
+                                let tag = tag.trim().to_lowercase();
+                                (format!("#{tag}"), seo::tag_path(&tag))
+                            })
+                            .into_iter()
+                            .chain([(post.title.clone(), format!("/post/{}", post.slug.clone().unwrap_or_default()))])
+                            .collect();
+                        let structured_data = seo::json_ld(vec![site.blog_posting_ld(post), site.breadcrumb_ld(crumbs)]);
 
                         rsx! {
                             document::Title { "{post.title}" }
@@ -60,6 +106,7 @@ pub fn Component(slug: String) -> Element {
                             document::Meta { name: "twitter:image", content: "{og_image}" }
                             document::Meta { name: "twitter:image:alt", content: "{post.title}" }
                             document::Link { rel: "canonical", href: "{canonical}" }
+                            document::Script { r#type: "application/ld+json", "{structured_data}" }
 
                             div { class: "w-full font-mono",
                                 div { class: "reading-progress" }
@@ -78,22 +125,34 @@ pub fn Component(slug: String) -> Element {
                                         p { class: "mt-3 text-sm leading-relaxed text-muted", "{post.summary}" }
 
                                         p { class: "mt-4 text-xs text-faint",
-                                            "author={post.author.name} date={post.created_at} read={post.read_time}min views={post.total_views}"
+                                            "author="
+                                            Link {
+                                                to: Route::Author { id: post.author.id.key.to_sql() },
+                                                class: "text-muted transition-colors duration-200 hover:text-accent",
+                                                "{post.author.name}"
+                                            }
+                                            " date={post.created_at} read={post.read_time}min views={post.total_views}"
                                         }
 
                                         if !post.tags.is_empty() {
-                                            {
-                                                let tags_str = post.tags.iter().take(10).cloned().collect::<Vec<_>>().join(", ");
-                                                rsx! {
-                                                    p { class: "mt-2 text-xs text-faint",
-                                                        span { class: "text-faint", "use " }
-                                                        span { class: "text-muted", "tags" }
-                                                        span { class: "text-faint", "::" }
-                                                        span { class: "text-faint", "{{" }
-                                                        span { class: "text-fg", "{tags_str}" }
-                                                        span { class: "text-faint", "}};" }
+                                            p { class: "mt-2 text-xs text-faint",
+                                                span { class: "text-faint", "use " }
+                                                span { class: "text-muted", "tags" }
+                                                span { class: "text-faint", "::" }
+                                                span { class: "text-faint", "{{" }
+                                                span { class: "text-fg",
+                                                    for (index, tag) in post.tags.iter().take(10).enumerate() {
+                                                        if index > 0 {
+                                                            ", "
+                                                        }
+                                                        Link {
+                                                            to: Route::Tag { tag: tag.trim().to_lowercase(), page: None },
+                                                            class: "transition-colors duration-200 hover:text-accent",
+                                                            "{tag}"
+                                                        }
                                                     }
                                                 }
+                                                span { class: "text-faint", "}};" }
                                             }
                                         }
 
@@ -108,9 +167,12 @@ pub fn Component(slug: String) -> Element {
                                         }
                                     }
 
-                                    div { class: "mt-4 rounded-lg border border-border bg-surface p-4 sm:p-6 md:p-8",
+                                    div { class: "relative mt-4 rounded-lg border border-border bg-surface p-4 sm:p-6 md:p-8",
+                                        if let Some(toc) = post.toc.clone().filter(|toc| toc.len() > 1) {
+                                            Toc { entries: toc }
+                                        }
                                         div {
-                                            class: "prose prose-base sm:prose-lg max-w-none break-words font-sans prose-pre:rounded-lg prose-pre:px-4 prose-pre:py-3 prose-pre:overflow-x-auto prose-pre:whitespace-pre prose-pre:max-w-full prose-code:bg-surface-2 prose-code:px-1.5 prose-code:py-0.5 prose-code:rounded prose-code:font-normal prose-code:before:content-none prose-code:after:content-none prose-code:break-words prose-a:break-all prose-p:leading-7 sm:prose-p:leading-8 prose-li:leading-7 [&_img]:h-auto [&_img]:max-w-full [&_table]:block [&_table]:max-w-full [&_table]:overflow-x-auto [&_table]:text-sm",
+                                            class: "post-body prose prose-base sm:prose-lg max-w-none break-words font-sans prose-pre:rounded-lg prose-pre:px-4 prose-pre:py-3 prose-pre:overflow-x-auto prose-pre:whitespace-pre prose-pre:max-w-full prose-code:bg-surface-2 prose-code:px-1.5 prose-code:py-0.5 prose-code:rounded prose-code:font-normal prose-code:before:content-none prose-code:after:content-none prose-code:break-words prose-a:break-all prose-p:leading-7 sm:prose-p:leading-8 prose-li:leading-7 [&_img]:h-auto [&_img]:max-w-full [&_table]:block [&_table]:max-w-full [&_table]:overflow-x-auto [&_table]:text-sm",
                                             dangerous_inner_html: "{post.body}"
                                         }
                                     }
@@ -122,14 +184,20 @@ pub fn Component(slug: String) -> Element {
                                                     p { class: "text-sm font-semibold text-fg", "Need Rust expertise?" }
                                                     p { class: "text-xs text-muted", "Build your next production Rust system with us." }
                                                 }
-                                                a {
-                                                    href: "mailto:info@rust-dd.com",
+                                                Link {
+                                                    to: Route::Contact {},
                                                     class: "inline-flex items-center justify-center rounded bg-accent px-4 py-2 text-xs font-semibold text-accent-fg transition-colors duration-200 hover:bg-accent/90",
                                                     "contact us"
                                                 }
                                             }
                                         }
                                     }
+
+                                    comments::Component { slug: post.slug.clone().unwrap_or_default() }
+
+                                    RelatedPosts { slug: post.slug.clone().unwrap_or_default() }
+
+                                    subscribe::Component {}
                                 }
                             }
                         }
@@ -150,3 +218,86 @@ pub fn Component(slug: String) -> Element {
         }
     }
 }
+
+#[component]
+fn Toc(entries: Vec<TocEntry>) -> Element {
+    let mut active = use_signal(String::new);
+
+    // Restarted for each post's headings when the component is reused.
+    use_effect(use_reactive!(|(entries)| {
+        let anchors = entries.into_iter().map(|entry| entry.anchor).collect::<Vec<_>>();
+        spawn(async move {
+            let mut eval = document::eval(TOC_SPY_JS);
+            let _ = eval.send(anchors);
+            while let Ok(anchor) = eval.recv::<String>().await {
+                active.set(anchor);
+            }
+        });
+    }));
+    use_drop(|| {
+        document::eval(TOC_SPY_STOP_JS);
+    });
+
+    // Only the two outermost heading levels in use, so deep nesting doesn't
+    // crowd the sidebar.
+    let top = entries.iter().map(|entry| entry.level).min().unwrap_or(1);
+
+    rsx! {
+        aside { class: "absolute top-0 left-full ml-8 hidden h-full w-56 2xl:block",
+            nav { class: "sticky top-20 max-h-[calc(100vh-6rem)] overflow-y-auto text-xs",
+                p { class: "text-faint", "// contents" }
+                ul { class: "mt-3 flex flex-col gap-1.5 border-l border-dashed border-border",
+                    for entry in entries.iter().filter(|entry| entry.level <= top + 1) {
+                        li { key: "{entry.anchor}",
+                            a {
+                                href: "#{entry.anchor}",
+                                class: if active() == entry.anchor {
+                                    "-ml-px block border-l border-accent text-accent"
+                                } else {
+                                    "-ml-px block border-l border-transparent text-muted transition-colors duration-200 hover:text-fg"
+                                },
+                                padding_left: if entry.level > top { "1.5rem" } else { "0.75rem" },
+                                "{entry.text}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn RelatedPosts(slug: String) -> Element {
+    let related = use_server_future(use_reactive!(|(slug)| async move {
+        select_related_posts(slug, RELATED_POSTS_LIMIT).await
+    }))?;
+
+    let Some(Ok(posts)) = related.read().as_ref().cloned() else {
+        return rsx! {};
+    };
+    if posts.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        section { class: "mt-8",
+            p { class: "text-xs text-faint", "// keep reading" }
+            div { class: "mt-3 grid gap-4 sm:grid-cols-3",
+                for post in posts {
+                    article {
+                        key: "{post.id.key.to_sql()}",
+                        class: "group rounded-lg border border-border bg-surface p-4 transition-colors duration-200 hover:border-accent",
+                        Link {
+                            to: Route::Post { slug: post.slug.clone().unwrap_or_default() },
+                            class: "flex h-full flex-col gap-2 no-underline",
+                            h2 { class: "text-sm leading-snug text-fg transition-colors duration-200 group-hover:text-accent", "{post.title}" }
+                            p { class: "line-clamp-3 text-xs leading-relaxed text-muted", "{post.summary}" }
+                            p { class: "mt-auto pt-2 text-xs text-faint", "{post.created_at} · {post.read_time}min" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use dioxus::prelude::*;
+
+use crate::{
+    app::Route,
+    components::{loader, tech_stack},
+    seo,
+    ssr::api::select_project,
+};
+
+#[component]
+pub fn Component(name: String) -> Element {
+    let site = seo::use_site();
+    let page = {
+        let name = name.clone();
+        use_server_future(use_reactive!(|(name)| async move { select_project(name).await }))?
+    };
+
+    let canonical = site.absolute_url(&format!("/projects/{name}"));
+
+    rsx! {
+        SuspenseBoundary {
+            fallback: |_| rsx! { loader::Inline { message: "Loading project...".to_string() } },
+            div { class: "w-full font-mono",
+                Link {
+                    to: Route::Projects {},
+                    class: "inline-flex gap-1 text-xs text-faint transition-colors duration-200 hover:text-accent",
+                    span { "<-" }
+                    span { "projects" }
+                }
+
+                if let Some(result) = page.read().as_ref() {
+                    match result {
+                        Ok(page) => {
+                            let project = &page.project;
+                            let title = site.title(&project.name);
+                            let og_image = project
+                                .screenshots
+                                .first()
+                                .map(|screenshot| {
+                                    if screenshot.src.starts_with("http") {
+                                        screenshot.src.clone()
+                                    } else {
+                                        site.absolute_url(&screenshot.src)
+                                    }
+                                })
+                                .unwrap_or_else(|| site.default_og_image.clone());
+                            let structured_data = seo::json_ld(vec![site.breadcrumb_ld(vec![
+                                ("Projects".to_string(), "/projects".to_string()),
+                                (project.name.clone(), format!("/projects/{name}")),
+                            ])]);
+
+                            rsx! {
+                                document::Title { "{title}" }
+                                document::Meta { name: "description", content: "{project.summary}" }
+                                document::Meta { name: "robots", content: "index, follow" }
+                                document::Meta { name: "googlebot", content: "index, follow" }
+                                document::Meta { property: "og:type", content: "article" }
+                                document::Meta { property: "og:title", content: "{title}" }
+                                document::Meta { property: "og:description", content: "{project.summary}" }
+                                document::Meta { property: "og:url", content: "{canonical}" }
+                                document::Meta { property: "og:image", content: "{og_image}" }
+                                document::Meta { name: "twitter:card", content: "summary_large_image" }
+                                document::Meta { name: "twitter:title", content: "{title}" }
+                                document::Meta { name: "twitter:description", content: "{project.summary}" }
+                                document::Meta { name: "twitter:url", content: "{canonical}" }
+                                document::Meta { name: "twitter:image", content: "{og_image}" }
+                                document::Link { rel: "canonical", href: "{canonical}" }
+                                document::Script { r#type: "application/ld+json", "{structured_data}" }
+
+                                section { class: "animate-rise py-4",
+                                    p { class: "text-xs text-faint", "// {project.kind}" }
+                                    h1 { class: "mt-2 text-3xl font-semibold leading-tight text-fg sm:text-4xl md:text-5xl",
+                                        "{project.name}"
+                                    }
+                                    p { class: "mt-3 max-w-2xl text-sm leading-relaxed text-muted", "{project.summary}" }
+                                }
+
+                                div { class: "mt-4 border-y border-dashed border-border py-3 text-xs text-muted",
+                                    div { class: "flex flex-wrap gap-x-4 gap-y-1",
+                                        span {
+                                            "live: "
+                                            a {
+                                                href: "{project.url}",
+                                                target: "_blank",
+                                                rel: "noopener noreferrer",
+                                                class: "text-fg transition-colors duration-200 hover:text-accent",
+                                                "visit ->"
+                                            }
+                                        }
+                                        if !project.tags.is_empty() {
+                                            span { class: "hidden sm:inline", "|" }
+                                            span {
+                                                "tags: "
+                                                span { class: "text-fg", {project.tags.join(", ")} }
+                                            }
+                                        }
+                                    }
+                                }
+
+                                if !project.body.is_empty() {
+                                    div {
+                                        class: "post-body prose prose-base mt-6 max-w-none break-words font-sans prose-pre:rounded-lg prose-pre:px-4 prose-pre:py-3 prose-pre:overflow-x-auto prose-code:bg-surface-2 prose-code:px-1.5 prose-code:py-0.5 prose-code:rounded prose-code:font-normal prose-code:before:content-none prose-code:after:content-none prose-p:leading-7 [&_img]:h-auto [&_img]:max-w-full",
+                                        dangerous_inner_html: "{project.body}"
+                                    }
+                                }
+
+                                if !project.screenshots.is_empty() {
+                                    section { class: "mt-8",
+                                        p { class: "mb-3 text-xs text-faint", "// screenshots" }
+                                        div { class: "grid gap-4 md:grid-cols-2",
+                                            for screenshot in project.screenshots.iter() {
+                                                figure {
+                                                    key: "{screenshot.src}",
+                                                    class: "overflow-hidden rounded-lg border border-border bg-surface",
+                                                    a { href: "{screenshot.src}", target: "_blank", rel: "noopener noreferrer",
+                                                        img {
+                                                            src: "{screenshot.src}",
+                                                            alt: screenshot.caption.clone().unwrap_or_else(|| format!("{} screenshot", project.name)),
+                                                            loading: "lazy",
+                                                            class: "h-auto w-full",
+                                                        }
+                                                    }
+                                                    if let Some(caption) = &screenshot.caption {
+                                                        figcaption { class: "px-3 py-2 text-xs text-muted", "{caption}" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+
+                                if !project.tech_stack.is_empty() {
+                                    section { class: "mt-8 rounded-lg border border-border bg-surface p-4 sm:p-5",
+                                        p { class: "mb-3 text-xs text-faint", "// tech stack" }
+                                        if project.tech_stack_percentage.is_empty() {
+                                            div { class: "flex flex-wrap gap-2 text-[11px] text-muted",
+                                                for tech in project.tech_stack.iter() {
+                                                    span { class: "rounded-full border border-border px-2 py-1", "{tech}" }
+                                                }
+                                            }
+                                        } else {
+                                            tech_stack::Component {
+                                                stack: project.tech_stack.clone(),
+                                                percentages: project.tech_stack_percentage.clone(),
+                                                legend: true,
+                                            }
+                                        }
+                                    }
+                                }
+
+                                if !page.related_posts.is_empty() {
+                                    section { class: "mt-8",
+                                        p { class: "mb-3 text-xs text-faint", "// related posts" }
+                                        ul { class: "flex flex-col gap-3",
+                                            for post in page.related_posts.iter() {
+                                                li { key: "{post.title}",
+                                                    Link {
+                                                        to: Route::Post { slug: post.slug.clone().unwrap_or_default() },
+                                                        class: "group block rounded-lg border border-border bg-surface p-4 transition-colors duration-200 hover:border-accent",
+                                                        p { class: "text-sm font-semibold text-fg group-hover:text-accent", "{post.title}" }
+                                                        p { class: "mt-1 line-clamp-2 text-xs leading-relaxed text-muted", "{post.summary}" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        Err(err) => rsx! {
+                            div { class: "mt-8 text-red-500", "Failed to load project: {err}" }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
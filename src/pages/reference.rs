@@ -0,0 +1,137 @@
+use dioxus::prelude::*;
+
+use crate::{
+    app::Route,
+    components::{loader, tech_stack},
+    seo,
+    ssr::api::select_reference,
+};
+
+#[component]
+pub fn Component(id: String) -> Element {
+    let site = seo::use_site();
+    let reference = {
+        let id = id.clone();
+        use_server_future(use_reactive!(|(id)| async move { select_reference(id).await }))?
+    };
+
+    let canonical = site.absolute_url(&format!("/references/{id}"));
+
+    rsx! {
+        SuspenseBoundary {
+            fallback: |_| rsx! { loader::Inline { message: "Loading reference...".to_string() } },
+            div { class: "w-full font-mono",
+                Link {
+                    to: Route::References { category: None, year: None },
+                    class: "inline-flex gap-1 text-xs text-faint transition-colors duration-200 hover:text-accent",
+                    span { "<-" }
+                    span { "references" }
+                }
+
+                if let Some(result) = reference.read().as_ref() {
+                    match result {
+                        Ok(reference) => {
+                            let title = site.title(&reference.title);
+                            let structured_data = seo::json_ld(vec![site.breadcrumb_ld(vec![
+                                ("References".to_string(), "/references".to_string()),
+                                (reference.title.clone(), format!("/references/{id}")),
+                            ])]);
+
+                            rsx! {
+                                document::Title { "{title}" }
+                                document::Meta { name: "description", content: "{reference.description}" }
+                                document::Meta { name: "robots", content: "index, follow" }
+                                document::Meta { name: "googlebot", content: "index, follow" }
+                                document::Meta { property: "og:type", content: "article" }
+                                document::Meta { property: "og:title", content: "{title}" }
+                                document::Meta { property: "og:description", content: "{reference.description}" }
+                                document::Meta { property: "og:url", content: "{canonical}" }
+                                document::Meta { property: "og:image", content: "{site.default_og_image}" }
+                                document::Meta { name: "twitter:card", content: "summary_large_image" }
+                                document::Meta { name: "twitter:title", content: "{title}" }
+                                document::Meta { name: "twitter:description", content: "{reference.description}" }
+                                document::Meta { name: "twitter:url", content: "{canonical}" }
+                                document::Meta { name: "twitter:image", content: "{site.default_og_image}" }
+                                document::Link { rel: "canonical", href: "{canonical}" }
+                                document::Script { r#type: "application/ld+json", "{structured_data}" }
+
+                                section { class: "animate-rise py-4",
+                                    p { class: "text-xs text-faint", "// case study" }
+                                    div { class: "mt-2 flex items-center gap-4",
+                                        if let Some(icon) = reference.icon.as_ref().filter(|icon| !icon.is_empty()) {
+                                            img { src: "{icon}", alt: "", class: "h-12 w-12 rounded object-contain" }
+                                        }
+                                        h1 { class: "text-3xl font-semibold leading-tight text-fg sm:text-4xl md:text-5xl",
+                                            "{reference.title}"
+                                        }
+                                    }
+                                }
+
+                                div { class: "mt-4 border-y border-dashed border-border py-3 text-xs text-muted",
+                                    div { class: "flex flex-wrap gap-x-4 gap-y-1",
+                                        if let Some(category) = &reference.category {
+                                            span {
+                                                "category: "
+                                                Link {
+                                                    to: Route::References { category: Some(category.clone()), year: None },
+                                                    class: "text-fg transition-colors duration-200 hover:text-accent",
+                                                    "{category}"
+                                                }
+                                            }
+                                        }
+                                        if let Some(year) = &reference.year {
+                                            span { class: "hidden sm:inline", "|" }
+                                            span {
+                                                "year: "
+                                                Link {
+                                                    to: Route::References { category: None, year: Some(year.clone()) },
+                                                    class: "text-fg transition-colors duration-200 hover:text-accent",
+                                                    "{year}"
+                                                }
+                                            }
+                                        }
+                                        if !reference.url.is_empty() {
+                                            span { class: "hidden sm:inline", "|" }
+                                            span {
+                                                "live: "
+                                                a {
+                                                    href: "{reference.url}",
+                                                    target: "_blank",
+                                                    rel: "noopener noreferrer",
+                                                    class: "text-muted transition-colors duration-200 hover:text-accent",
+                                                    "visit ->"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+
+                                p { class: "mt-6 whitespace-pre-line text-sm leading-relaxed text-muted", "{reference.description}" }
+
+                                section { class: "mt-8 rounded-lg border border-border bg-surface p-4 sm:p-5",
+                                    p { class: "mb-3 text-xs text-faint", "// tech stack" }
+                                    tech_stack::Component {
+                                        stack: reference.tech_stack.clone(),
+                                        percentages: reference.teck_stack_percentage.clone(),
+                                        legend: true,
+                                    }
+                                }
+
+                                if !reference.tags.is_empty() {
+                                    div { class: "mt-4 flex flex-wrap gap-2 text-[11px] text-muted",
+                                        for tag in reference.tags.iter() {
+                                            span { class: "rounded-full border border-border px-2 py-1", "{tag}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        Err(err) => rsx! {
+                            div { class: "mt-8 text-red-500", "Failed to load reference: {err}" }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::BTreeSet;
+
+use dioxus::prelude::*;
+use surrealdb_types::ToSql;
+
+use crate::{
+    app::Route,
+    components::{loader, tech_stack},
+    seo,
+    ssr::{api::select_references, types::Reference},
+};
+
+const CHIP_CLASS: &str =
+    "rounded-full border px-2 py-1 transition-colors duration-200 hover:border-accent hover:text-accent";
+
+#[component]
+pub fn Component(category: Option<String>, year: Option<String>) -> Element {
+    let site = seo::use_site();
+    let references = use_server_future(select_references)?;
+
+    let title = site.title("References");
+    let description = format!("Client work and case studies from {}.", site.short_name);
+    let canonical = site.absolute_url("/references");
+    let structured_data = seo::json_ld(vec![
+        site.breadcrumb_ld(vec![("References".to_string(), "/references".to_string())])
+    ]);
+
+    rsx! {
+        document::Title { "{title}" }
+        document::Meta { name: "description", content: "{description}" }
+        document::Meta { name: "robots", content: "index, follow" }
+        document::Meta { name: "googlebot", content: "index, follow" }
+        document::Meta { property: "og:type", content: "website" }
+        document::Meta { property: "og:title", content: "{title}" }
+        document::Meta { property: "og:description", content: "{description}" }
+        document::Meta { property: "og:url", content: "{canonical}" }
+        document::Meta { property: "og:image", content: "{site.default_og_image}" }
+        document::Meta { name: "twitter:card", content: "summary_large_image" }
+        document::Meta { name: "twitter:title", content: "{title}" }
+        document::Meta { name: "twitter:description", content: "{description}" }
+        document::Meta { name: "twitter:url", content: "{canonical}" }
+        document::Meta { name: "twitter:image", content: "{site.default_og_image}" }
+        document::Link { rel: "canonical", href: "{canonical}" }
+        document::Script { r#type: "application/ld+json", "{structured_data}" }
+
+        SuspenseBoundary {
+            fallback: |_| rsx! { loader::Inline { message: "Loading references...".to_string() } },
+            div { class: "w-full font-mono",
+                section { class: "animate-rise py-4",
+                    p { class: "text-xs text-faint", "// references" }
+                    h1 { class: "mt-2 text-3xl font-semibold leading-tight text-fg sm:text-4xl md:text-5xl",
+                        "References"
+                    }
+                    p { class: "mt-3 max-w-2xl text-sm leading-relaxed text-muted",
+                        "Selected client work: what we built, and what we built it with."
+                    }
+                }
+
+                if let Some(result) = references.read().as_ref() {
+                    match result {
+                        Ok(references) => {
+                            let categories = references
+                                .iter()
+                                .filter_map(|reference| reference.category.clone())
+                                .collect::<BTreeSet<_>>();
+                            let years = references
+                                .iter()
+                                .filter_map(|reference| reference.year.clone())
+                                .collect::<BTreeSet<_>>();
+                            let shown = references
+                                .iter()
+                                .filter(|reference| category.is_none() || reference.category == category)
+                                .filter(|reference| year.is_none() || reference.year == year)
+                                .cloned()
+                                .collect::<Vec<_>>();
+
+                            rsx! {
+                                div { class: "mt-4 flex flex-col gap-2 border-y border-dashed border-border py-3 text-xs text-muted",
+                                    div { class: "flex flex-wrap items-center gap-2",
+                                        span { class: "w-16 text-faint", "category" }
+                                        Link {
+                                            to: Route::References { category: None, year: year.clone() },
+                                            class: chip_class(category.is_none()),
+                                            "all"
+                                        }
+                                        for option in categories {
+                                            Link {
+                                                key: "{option}",
+                                                to: Route::References { category: Some(option.clone()), year: year.clone() },
+                                                class: chip_class(category.as_ref() == Some(&option)),
+                                                "{option}"
+                                            }
+                                        }
+                                    }
+                                    div { class: "flex flex-wrap items-center gap-2",
+                                        span { class: "w-16 text-faint", "year" }
+                                        Link {
+                                            to: Route::References { category: category.clone(), year: None },
+                                            class: chip_class(year.is_none()),
+                                            "all"
+                                        }
+                                        for option in years.into_iter().rev() {
+                                            Link {
+                                                key: "{option}",
+                                                to: Route::References { category: category.clone(), year: Some(option.clone()) },
+                                                class: chip_class(year.as_ref() == Some(&option)),
+                                                "{option}"
+                                            }
+                                        }
+                                    }
+                                    span {
+                                        "showing: "
+                                        span { class: "text-fg", "{shown.len()}/{references.len()}" }
+                                    }
+                                }
+
+                                if shown.is_empty() {
+                                    p { class: "mt-6 text-sm text-muted", "No references match these filters." }
+                                }
+                                section { class: "mt-6 grid gap-4 md:grid-cols-2",
+                                    for reference in shown {
+                                        ReferenceCard { key: "{reference.id.key.to_sql()}", reference }
+                                    }
+                                }
+                            }
+                        }
+                        Err(err) => rsx! {
+                            div { class: "mt-8 text-red-500", "Failed to load references: {err}" }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn chip_class(active: bool) -> String {
+    if active {
+        format!("{CHIP_CLASS} border-accent text-accent")
+    } else {
+        format!("{CHIP_CLASS} border-border")
+    }
+}
+
+#[component]
+fn ReferenceCard(reference: Reference) -> Element {
+    let meta = [reference.category.clone(), reference.year.clone()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ");
+    let stack = reference.tech_stack.join(" · ");
+
+    rsx! {
+        Link {
+            to: Route::Reference { id: reference.id.key.to_sql() },
+            class: "group flex flex-col justify-between gap-4 rounded-xl border border-border bg-surface p-5 no-underline transition-colors duration-200 hover:border-accent hover:bg-surface-2",
+            div {
+                div { class: "flex items-center gap-3",
+                    if let Some(icon) = reference.icon.as_ref().filter(|icon| !icon.is_empty()) {
+                        img { src: "{icon}", alt: "", class: "h-8 w-8 rounded object-contain" }
+                    }
+                    div {
+                        p { class: "text-[11px] uppercase tracking-[0.2em] text-faint", "{meta}" }
+                        h2 { class: "text-xl font-semibold text-fg", "{reference.title}" }
+                    }
+                }
+                p { class: "mt-3 line-clamp-3 text-sm leading-relaxed text-muted", "{reference.description}" }
+            }
+            div {
+                tech_stack::Component {
+                    stack: reference.tech_stack.clone(),
+                    percentages: reference.teck_stack_percentage.clone(),
+                }
+                p { class: "mt-2 text-xs text-faint", "{stack}" }
+                span { class: "mt-3 block text-sm text-faint transition-colors duration-200 group-hover:text-accent",
+                    "case study ->"
+                }
+            }
+        }
+    }
+}
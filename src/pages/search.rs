@@ -0,0 +1,109 @@
+use dioxus::prelude::*;
+
+use crate::{app::Route, components::loader, seo, ssr::api::search_posts};
+
+#[component]
+pub fn Component(q: String) -> Element {
+    let site = seo::use_site();
+    let mut input = use_signal(|| q.clone());
+    let results = {
+        let q = q.clone();
+        use_server_future(use_reactive!(|(q)| async move { search_posts(q).await }))?
+    };
+
+    let query = q.trim().to_string();
+    let title = if query.is_empty() {
+        site.title("Search")
+    } else {
+        site.title(&format!("{query} · Search"))
+    };
+    let description = format!("Search posts on the {} blog.", site.short_name);
+    let canonical = site.absolute_url("/search");
+
+    rsx! {
+        document::Title { "{title}" }
+        document::Meta { name: "description", content: "{description}" }
+        document::Meta { name: "robots", content: "noindex, follow" }
+        document::Meta { name: "googlebot", content: "noindex, follow" }
+        document::Meta { property: "og:type", content: "website" }
+        document::Meta { property: "og:title", content: "{title}" }
+        document::Meta { property: "og:description", content: "{description}" }
+        document::Meta { property: "og:url", content: "{canonical}" }
+        document::Meta { name: "twitter:card", content: "summary" }
+        document::Meta { name: "twitter:title", content: "{title}" }
+        document::Meta { name: "twitter:description", content: "{description}" }
+        document::Meta { name: "twitter:url", content: "{canonical}" }
+        document::Link { rel: "canonical", href: "{canonical}" }
+
+        div { class: "w-full font-mono",
+            section { class: "animate-rise py-4",
+                p { class: "text-xs text-faint", "// search" }
+                h1 { class: "mt-2 text-3xl font-semibold leading-tight text-fg sm:text-4xl md:text-5xl",
+                    "Search"
+                }
+            }
+
+            form {
+                class: "mt-4 flex items-center gap-2 border-y border-dashed border-border py-3 text-sm",
+                onsubmit: move |evt: FormEvent| {
+                    evt.prevent_default();
+                    navigator().push(Route::Search { q: input().trim().to_string() });
+                },
+                span { class: "text-accent", "grep" }
+                input {
+                    r#type: "search",
+                    name: "q",
+                    value: "{input}",
+                    placeholder: "keywords, tags...",
+                    autofocus: true,
+                    class: "min-w-0 flex-1 bg-transparent text-fg placeholder:text-faint focus:outline-none",
+                    oninput: move |evt| input.set(evt.value()),
+                }
+                button {
+                    r#type: "submit",
+                    class: "rounded bg-accent px-3 py-1 text-xs font-semibold text-accent-fg transition-colors duration-200 hover:bg-accent/90",
+                    "search"
+                }
+            }
+
+            SuspenseBoundary {
+                fallback: |_| rsx! { loader::Inline { message: "Searching...".to_string() } },
+                if let Some(result) = results.read().as_ref() {
+                    match result {
+                        Ok(_) if query.is_empty() => rsx! {
+                            p { class: "mt-6 text-xs text-faint", "// type a query to search all posts" }
+                        },
+                        Ok(items) if items.is_empty() => rsx! {
+                            p { class: "mt-6 text-sm text-muted", "No posts match \"{query}\"." }
+                        },
+                        Ok(items) => rsx! {
+                            p { class: "mt-6 text-xs text-faint", "// {items.len()} results" }
+                            div { class: "mt-3 rounded-lg border border-border bg-surface",
+                                div { class: "divide-y divide-border",
+                                    for item in items.iter() {
+                                        Link {
+                                            to: Route::Post { slug: item.slug.clone().unwrap_or_default() },
+                                            class: "block px-4 py-3 no-underline transition-colors duration-150 hover:bg-surface-2 [&_mark]:bg-accent/20 [&_mark]:text-fg",
+                                            p { class: "text-sm text-fg", dangerous_inner_html: "{item.title}" }
+                                            p { class: "mt-1 text-sm leading-relaxed text-muted", dangerous_inner_html: "{item.snippet}" }
+                                            p { class: "mt-1 text-xs text-faint",
+                                                "{item.created_at} · {item.read_time}min"
+                                                if !item.tags.is_empty() {
+                                                    " · "
+                                                    {item.tags.join(", ")}
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        Err(err) => rsx! {
+                            div { class: "mt-8 text-red-500", "Search failed: {err}" }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use dioxus::prelude::*;
+
+use crate::{app::Route, components::loader, seo, ssr::api::select_posts_by_tag};
+
+#[component]
+pub fn Component(tag: String, page: Option<usize>) -> Element {
+    let site = seo::use_site();
+    let tag = tag.trim().to_lowercase();
+    let posts = {
+        let tag = tag.clone();
+        use_server_future(use_reactive!(|(tag, page)| async move {
+            select_posts_by_tag(tag, page.unwrap_or(1)).await
+        }))?
+    };
+
+    let page_number = page.unwrap_or(1).max(1);
+    let title = if page_number > 1 {
+        site.title(&format!("#{tag} · page {page_number}"))
+    } else {
+        site.title(&format!("#{tag}"))
+    };
+    let description = format!("Posts tagged #{tag} on the {} blog.", site.short_name);
+    let tag_path = seo::tag_path(&tag);
+    let canonical = if page_number > 1 {
+        site.absolute_url(&format!("{tag_path}?page={page_number}"))
+    } else {
+        site.absolute_url(&tag_path)
+    };
+    let feed = site.absolute_url(&format!("{tag_path}/rss.xml"));
+    let structured_data = seo::json_ld(vec![site.breadcrumb_ld(vec![(format!("#{tag}"), tag_path.clone())])]);
+
+    rsx! {
+        document::Title { "{title}" }
+        document::Meta { name: "description", content: "{description}" }
+        document::Meta { name: "robots", content: "index, follow" }
+        document::Meta { name: "googlebot", content: "index, follow" }
+        document::Meta { property: "og:type", content: "website" }
+        document::Meta { property: "og:title", content: "{title}" }
+        document::Meta { property: "og:description", content: "{description}" }
+        document::Meta { property: "og:url", content: "{canonical}" }
+        document::Meta { property: "og:image", content: "{site.default_og_image}" }
+        document::Meta { name: "twitter:card", content: "summary_large_image" }
+        document::Meta { name: "twitter:title", content: "{title}" }
+        document::Meta { name: "twitter:description", content: "{description}" }
+        document::Meta { name: "twitter:url", content: "{canonical}" }
+        document::Meta { name: "twitter:image", content: "{site.default_og_image}" }
+        document::Link { rel: "canonical", href: "{canonical}" }
+        document::Script { r#type: "application/ld+json", "{structured_data}" }
+        document::Link {
+            rel: "alternate",
+            r#type: "application/rss+xml",
+            title: "{site.short_name} #{tag} RSS Feed",
+            href: "{feed}"
+        }
+
+        SuspenseBoundary {
+            fallback: |_| rsx! { loader::Inline { message: "Loading posts...".to_string() } },
+            div { class: "w-full font-mono",
+                Link {
+                    to: Route::Home {},
+                    class: "inline-flex gap-1 text-xs text-faint transition-colors duration-200 hover:text-accent",
+                    span { "<-" }
+                    span { "back" }
+                }
+
+                section { class: "animate-rise py-4",
+                    p { class: "text-xs text-faint", "// tag" }
+                    h1 { class: "mt-2 text-3xl font-semibold leading-tight text-fg sm:text-4xl md:text-5xl",
+                        span { class: "text-accent", "#" }
+                        "{tag}"
+                    }
+                }
+
+                if let Some(result) = posts.read().as_ref() {
+                    match result {
+                        Ok(result) => rsx! {
+                            div { class: "mt-4 border-y border-dashed border-border py-3 text-xs text-muted",
+                                div { class: "flex flex-wrap gap-x-4 gap-y-1",
+                                    span { "posts: " span { class: "text-fg", "{result.total_posts}" } }
+                                    span { class: "hidden sm:inline", "|" }
+                                    span { "page: " span { class: "text-fg", "{result.page}/{result.total_pages}" } }
+                                    span { class: "hidden sm:inline", "|" }
+                                    span {
+                                        "feed: "
+                                        a {
+                                            href: "{tag_path}/rss.xml",
+                                            target: "_blank",
+                                            rel: "noopener noreferrer",
+                                            class: "text-muted transition-colors duration-200 hover:text-accent",
+                                            "rss"
+                                        }
+                                    }
+                                }
+                            }
+
+                            section { class: "mt-6",
+                                div { class: "rounded-lg border border-border bg-surface",
+                                    div { class: "divide-y divide-border",
+                                        for post in result.posts.iter() {
+                                            Link {
+                                                to: Route::Post { slug: post.slug.clone().unwrap_or_default() },
+                                                class: "block px-4 py-3 no-underline transition-colors duration-150 hover:bg-surface-2",
+                                                p { class: "text-sm text-fg", "{post.title}" }
+                                                p { class: "mt-1 text-sm leading-relaxed text-muted", "{post.summary}" }
+                                                p { class: "mt-1 text-xs text-faint",
+                                                    "{post.created_at} · {post.read_time}min · {post.total_views} views"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
+                            if result.total_pages > 1 {
+                                nav { class: "mt-4 flex items-center justify-between text-xs text-muted",
+                                    if result.page > 1 {
+                                        Link {
+                                            to: Route::Tag { tag: tag.clone(), page: Some(result.page - 1) },
+                                            class: "transition-colors duration-200 hover:text-accent",
+                                            "<- newer"
+                                        }
+                                    } else {
+                                        span {}
+                                    }
+                                    if result.page < result.total_pages {
+                                        Link {
+                                            to: Route::Tag { tag: tag.clone(), page: Some(result.page + 1) },
+                                            class: "transition-colors duration-200 hover:text-accent",
+                                            "older ->"
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        Err(err) => rsx! {
+                            div { class: "mt-8 text-red-500", "Failed to load posts: {err}" }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,15 +1,373 @@
-pub const SITE_NAME: &str = "Rust-DD Blog - Tech Insights & Consulting";
-pub const SITE_DESCRIPTION: &str = "Explore open-source Rust projects, learn innovative techniques, and connect with a passionate community. Get expert Rust development and consulting services.";
-pub const SITE_URL: &str = "https://rust-dd.com";
-pub const DEFAULT_OG_IMAGE: &str = "https://static.rust-dd.com/rust-dd_custom_bg.png";
-pub const X_HANDLE: &str = "@rust_dd";
-
-pub fn absolute_url(path: &str) -> String {
-    let normalized = if path.starts_with('/') {
-        path.to_string()
-    } else {
-        format!("/{path}")
-    };
-
-    format!("{SITE_URL}{normalized}")
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use surrealdb_types::ToSql;
+
+use crate::ssr::types::{Author, Post};
+
+/// Identity of the site being served: its public URL, names and social
+/// defaults. Loaded once on the server by `site()`; components read the copy
+/// `App` provides with `use_site()`, which the client gets during hydration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SiteConfig {
+    /// Public origin without a trailing slash, e.g. `https://rust-dd.com`.
+    pub url: String,
+    /// Full title, used on the home page and as `og:site_name`.
+    pub name: String,
+    /// Brand used as the page title suffix and in feed titles.
+    pub short_name: String,
+    pub description: String,
+    pub default_og_image: String,
+    pub x_handle: String,
+}
+
+impl Default for SiteConfig {
+    fn default() -> Self {
+        Self {
+            url: "https://rust-dd.com".to_string(),
+            name: "Rust-DD Blog - Tech Insights & Consulting".to_string(),
+            short_name: "Rust-DD".to_string(),
+            description: "Explore open-source Rust projects, learn innovative techniques, and connect with a passionate community. Get expert Rust development and consulting services.".to_string(),
+            default_og_image: "https://static.rust-dd.com/rust-dd_custom_bg.png".to_string(),
+            x_handle: "@rust_dd".to_string(),
+        }
+    }
+}
+
+impl SiteConfig {
+    pub fn absolute_url(&self, path: &str) -> String {
+        let normalized = if path.starts_with('/') {
+            path.to_string()
+        } else {
+            format!("/{path}")
+        };
+
+        format!("{}{normalized}", self.url)
+    }
+
+    /// `{title} | {short_name}`, the title of every page but the home page.
+    pub fn title(&self, title: &str) -> String {
+        format!("{title} | {}", self.short_name)
+    }
+
+    /// Share image for a post: its header image, else the card rendered at
+    /// `/og/{slug}.png`.
+    pub fn post_og_image(&self, post: &Post) -> String {
+        post.header_image
+            .clone()
+            .unwrap_or_else(|| self.absolute_url(&format!("/og/{}.png", post.slug.clone().unwrap_or_default())))
+    }
+
+    /// Reads the TOML file at `SITE_CONFIG` (default `site.toml`, optional),
+    /// then applies `SITE_URL`, `SITE_NAME`, `SITE_SHORT_NAME`,
+    /// `SITE_DESCRIPTION`, `SITE_OG_IMAGE` and `SITE_X_HANDLE` on top. Fields
+    /// set nowhere keep the rust-dd.com defaults.
+    #[cfg(feature = "server")]
+    pub fn load() -> Result<Self, String> {
+        use std::env;
+
+        let path = env::var("SITE_CONFIG").ok();
+        let mut config = match std::fs::read_to_string(path.as_deref().unwrap_or("site.toml")) {
+            Ok(contents) => toml::from_str::<SiteConfig>(&contents).map_err(|err| err.to_string())?,
+            Err(err) if path.is_none() && err.kind() == std::io::ErrorKind::NotFound => SiteConfig::default(),
+            Err(err) => return Err(format!("failed to read {}: {err}", path.unwrap_or_default())),
+        };
+
+        for (name, field) in [
+            ("SITE_URL", &mut config.url),
+            ("SITE_NAME", &mut config.name),
+            ("SITE_SHORT_NAME", &mut config.short_name),
+            ("SITE_DESCRIPTION", &mut config.description),
+            ("SITE_OG_IMAGE", &mut config.default_og_image),
+            ("SITE_X_HANDLE", &mut config.x_handle),
+        ] {
+            if let Ok(value) = env::var(name) {
+                *field = value;
+            }
+        }
+
+        config.url = config.url.trim_end_matches('/').to_string();
+        if !config.url.starts_with("https://") && !config.url.starts_with("http://") {
+            return Err(format!(
+                "site url must start with http:// or https://, got {:?}",
+                config.url
+            ));
+        }
+
+        Ok(config)
+    }
+
+    /// The server's config, or the defaults when rendered without a server.
+    pub fn current() -> Self {
+        #[cfg(feature = "server")]
+        {
+            site().clone()
+        }
+        #[cfg(not(feature = "server"))]
+        {
+            SiteConfig::default()
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+static SITE: std::sync::LazyLock<SiteConfig> =
+    std::sync::LazyLock::new(|| SiteConfig::load().unwrap_or_else(|err| panic!("invalid site config: {err}")));
+
+/// The site config for server-side code such as feeds, the sitemap and
+/// emails. `main` loads it at startup so a bad config fails fast.
+#[cfg(feature = "server")]
+pub fn site() -> &'static SiteConfig {
+    &SITE
+}
+
+/// schema.org structured data. The builders return bare nodes that
+/// `json_ld` wraps in a single `@graph`, so nodes can refer to each other by
+/// `@id` (every page's `WebSite` and posts' `publisher` point at the same
+/// `Organization`).
+impl SiteConfig {
+    fn organization_id(&self) -> String {
+        self.absolute_url("/#organization")
+    }
+
+    pub fn organization_ld(&self) -> Value {
+        json!({
+            "@type": "Organization",
+            "@id": self.organization_id(),
+            "name": self.short_name,
+            "url": self.url,
+            "logo": self.default_og_image,
+        })
+    }
+
+    /// `WebSite` with a `SearchAction` for `/search`, which lets search
+    /// engines offer a search box for the site.
+    pub fn website_ld(&self) -> Value {
+        json!({
+            "@type": "WebSite",
+            "@id": self.absolute_url("/#website"),
+            "name": self.name,
+            "description": self.description,
+            "url": self.url,
+            "publisher": { "@id": self.organization_id() },
+            "potentialAction": {
+                "@type": "SearchAction",
+                "target": {
+                    "@type": "EntryPoint",
+                    "urlTemplate": self.absolute_url("/search?q={search_term_string}"),
+                },
+                "query-input": "required name=search_term_string",
+            },
+        })
+    }
+
+    pub fn person_ld(&self, author: &Author) -> Value {
+        let same_as = [author.github_url(), author.twitter_url(), author.linkedin_url()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        let mut person = json!({
+            "@type": "Person",
+            "name": author.name,
+            "url": self.absolute_url(&format!("/author/{}", author.id.key.to_sql())),
+            "sameAs": same_as,
+        });
+        if let Some(bio) = &author.bio {
+            person["description"] = json!(bio);
+        }
+        person
+    }
+
+    /// `BlogPosting` for a post loaded by `select_post`; dates come from
+    /// `published_at` and `edited_at`.
+    pub fn blog_posting_ld(&self, post: &Post) -> Value {
+        let url = self.absolute_url(&format!("/post/{}", post.slug.clone().unwrap_or_default()));
+
+        json!({
+            "@type": "BlogPosting",
+            "headline": post.title,
+            "description": post.summary,
+            "url": url,
+            "mainEntityOfPage": url,
+            "image": self.post_og_image(post),
+            "datePublished": post.published_at,
+            "dateModified": post.edited_at,
+            "keywords": post.tags.join(", "),
+            "author": self.person_ld(&post.author),
+            "publisher": { "@id": self.organization_id() },
+        })
+    }
+
+    /// `BreadcrumbList` from `(name, path)` pairs, starting after the home page.
+    pub fn breadcrumb_ld(&self, crumbs: Vec<(String, String)>) -> Value {
+        let items = std::iter::once(("Home".to_string(), "/".to_string()))
+            .chain(crumbs)
+            .enumerate()
+            .map(|(index, (name, path))| {
+                json!({
+                    "@type": "ListItem",
+                    "position": index + 1,
+                    "name": name,
+                    "item": self.absolute_url(&path),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({ "@type": "BreadcrumbList", "itemListElement": items })
+    }
+}
+
+/// Serializes nodes into one schema.org `@graph` for a
+/// `<script type="application/ld+json">`, escaping `<` so text in the data
+/// can't close the script tag.
+pub fn json_ld(nodes: Vec<Value>) -> String {
+    json!({ "@context": "https://schema.org", "@graph": nodes })
+        .to_string()
+        .replace('<', "\\u003c")
+}
+
+/// `/tag/{tag}` with the tag percent-encoded, so tags holding `#`, `?`, `/`
+/// or spaces stay one path segment.
+pub fn tag_path(tag: &str) -> String {
+    format!("/tag/{}", urlencoding::encode(tag))
+}
+
+/// The site config provided by `App`.
+pub fn use_site() -> SiteConfig {
+    use_context::<SiteConfig>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use surrealdb_types::RecordId;
+
+    fn site() -> SiteConfig {
+        SiteConfig {
+            url: "https://blog.example.com".to_string(),
+            short_name: "Example".to_string(),
+            ..SiteConfig::default()
+        }
+    }
+
+    fn author() -> Author {
+        Author {
+            id: RecordId::new("author", "jane"),
+            name: "Jane Doe".to_string(),
+            bio: Some("Writes Rust.".to_string()),
+            github: Some("janedoe".to_string()),
+            twitter: Some("@jane".to_string()),
+            linkedin: Some("https://www.linkedin.com/in/jane-doe".to_string()),
+            ..Author::default()
+        }
+    }
+
+    fn post() -> Post {
+        Post {
+            title: "Async </script> in Rust".to_string(),
+            summary: "A tour of futures.".to_string(),
+            tags: vec!["rust".to_string(), "async".to_string()],
+            author: author(),
+            slug: Some("async-rust".to_string()),
+            updated_at: "2024-03-05T00:00:00Z".to_string(),
+            edited_at: "2024-03-02T10:00:00Z".to_string(),
+            published_at: Some("2024-03-01T09:00:00+00:00".to_string()),
+            ..Post::default()
+        }
+    }
+
+    /// Round-trips through `json_ld`, which is what ends up in the page.
+    fn graph(nodes: Vec<Value>) -> Vec<Value> {
+        let document: Value = serde_json::from_str(&json_ld(nodes)).expect("json_ld emits valid JSON");
+        assert_eq!(document["@context"], "https://schema.org");
+        document["@graph"].as_array().expect("@graph is an array").clone()
+    }
+
+    #[test]
+    fn blog_posting_has_dates_author_and_publisher() {
+        let site = site();
+        let nodes = graph(vec![site.blog_posting_ld(&post()), site.organization_ld()]);
+        let (posting, organization) = (&nodes[0], &nodes[1]);
+
+        assert_eq!(posting["@type"], "BlogPosting");
+        assert_eq!(posting["headline"], "Async </script> in Rust");
+        assert_eq!(posting["url"], "https://blog.example.com/post/async-rust");
+        assert_eq!(posting["mainEntityOfPage"], posting["url"]);
+        assert_eq!(posting["image"], "https://blog.example.com/og/async-rust.png");
+        assert_eq!(posting["datePublished"], "2024-03-01T09:00:00+00:00");
+        assert_eq!(posting["dateModified"], "2024-03-02T10:00:00Z");
+        assert_eq!(posting["keywords"], "rust, async");
+        assert_eq!(posting["author"]["@type"], "Person");
+        assert_eq!(posting["author"]["url"], "https://blog.example.com/author/jane");
+        assert_eq!(posting["publisher"]["@id"], organization["@id"]);
+        assert_eq!(organization["@type"], "Organization");
+        assert_eq!(organization["name"], "Example");
+    }
+
+    #[test]
+    fn person_links_handles_and_urls() {
+        let person = site().person_ld(&author());
+
+        assert_eq!(person["name"], "Jane Doe");
+        assert_eq!(
+            person["sameAs"],
+            json!([
+                "https://github.com/janedoe",
+                "https://x.com/jane",
+                "https://www.linkedin.com/in/jane-doe",
+            ])
+        );
+    }
+
+    #[test]
+    fn website_search_action_targets_search_page() {
+        let website = site().website_ld();
+        let action = &website["potentialAction"];
+
+        assert_eq!(website["@type"], "WebSite");
+        assert_eq!(website["url"], "https://blog.example.com");
+        assert_eq!(action["@type"], "SearchAction");
+        assert_eq!(
+            action["target"]["urlTemplate"],
+            "https://blog.example.com/search?q={search_term_string}"
+        );
+        assert_eq!(action["query-input"], "required name=search_term_string");
+    }
+
+    #[test]
+    fn breadcrumbs_start_at_home_and_count_from_one() {
+        let breadcrumbs = site().breadcrumb_ld(vec![
+            ("#rust".to_string(), "/tag/rust".to_string()),
+            ("Async".to_string(), "/post/async-rust".to_string()),
+        ]);
+        let items = breadcrumbs["itemListElement"].as_array().unwrap();
+
+        assert_eq!(breadcrumbs["@type"], "BreadcrumbList");
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0]["name"], "Home");
+        assert_eq!(items[0]["item"], "https://blog.example.com/");
+        for (index, item) in items.iter().enumerate() {
+            assert_eq!(item["@type"], "ListItem");
+            assert_eq!(item["position"], index + 1);
+        }
+        assert_eq!(items[2]["item"], "https://blog.example.com/post/async-rust");
+    }
+
+    #[test]
+    fn json_ld_cannot_close_its_script_tag() {
+        let html = json_ld(vec![site().blog_posting_ld(&post())]);
+
+        assert!(!html.contains("</script>"));
+        assert!(html.contains("\\u003c/script>"));
+    }
+
+    #[test]
+    fn tag_paths_keep_the_tag_one_segment() {
+        assert_eq!(tag_path("rust"), "/tag/rust");
+        assert_eq!(tag_path("c#"), "/tag/c%23");
+        assert_eq!(tag_path("a/b?c"), "/tag/a%2Fb%3Fc");
+        assert_eq!(tag_path("machine learning"), "/tag/machine%20learning");
+    }
 }
@@ -1,9 +1,27 @@
+pub mod admin;
+#[cfg(feature = "server")]
+pub mod analytics;
 pub mod api;
 #[cfg(feature = "server")]
+pub mod auth;
+pub mod comments;
+pub mod contact;
+#[cfg(feature = "server")]
+pub mod github;
+#[cfg(feature = "server")]
+pub mod mailer;
+pub mod newsletter;
+#[cfg(feature = "server")]
+pub mod og_image;
+#[cfg(feature = "server")]
+pub mod publisher;
+#[cfg(feature = "server")]
 pub mod redirect;
 #[cfg(feature = "server")]
 pub mod server_utils;
 pub mod types;
+#[cfg(feature = "server")]
+pub mod views;
 
 #[cfg(feature = "server")]
 pub mod app_state {
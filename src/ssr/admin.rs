@@ -0,0 +1,389 @@
+use crate::ssr::types::{AnalyticsReport, Author, PendingComment, Post, PostInput, RenderedMarkdown};
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+fn validate_post_input(input: &PostInput) -> Result<(), HttpError> {
+    if input.title.trim().is_empty() {
+        return HttpError::bad_request("title is required");
+    }
+    if input.summary.trim().is_empty() {
+        return HttpError::bad_request("summary is required");
+    }
+    if input.body.trim().is_empty() {
+        return HttpError::bad_request("body is required");
+    }
+    if let Some(image) = &input.header_image {
+        if !image.starts_with("https://") && !image.starts_with('/') {
+            return HttpError::bad_request("header image must be an https:// or site-relative url");
+        }
+    }
+    Ok(())
+}
+
+#[post("/api/admin/login")]
+pub async fn admin_login(username: String, password: String) -> Result<()> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::auth::{issue_session, verify_credentials};
+
+        verify_credentials(&username, &password).await?;
+        issue_session(&username)?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[post("/api/admin/logout")]
+pub async fn admin_logout() -> Result<()> {
+    #[cfg(feature = "server")]
+    {
+        crate::ssr::auth::clear_session();
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[get("/api/admin/session")]
+pub async fn admin_session() -> Result<Option<String>> {
+    #[cfg(feature = "server")]
+    {
+        Ok(crate::ssr::auth::require_admin().await.ok())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[get("/api/admin/posts")]
+pub async fn admin_select_posts() -> Result<Vec<Post>> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::auth::require_admin;
+
+        require_admin().await?;
+
+        let db = db().await;
+        let mut query = db
+            .query("SELECT *, author.*, <string>created_at AS created_at, <string>updated_at AS updated_at, <string>(edited_at ?? created_at) AS edited_at FROM post ORDER BY is_published, created_at DESC;")
+            .await?;
+
+        Ok(query.take::<Vec<Post>>(0)?)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[get("/api/admin/authors")]
+pub async fn admin_select_authors() -> Result<Vec<Author>> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::auth::require_admin;
+
+        require_admin().await?;
+
+        let db = db().await;
+        let mut query = db.query("SELECT * FROM author ORDER BY name;").await?;
+
+        Ok(query.take::<Vec<Author>>(0)?)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+/// Returns the post with its raw markdown body, drafts included.
+#[get("/api/admin/posts/{id}")]
+pub async fn admin_select_post(id: String) -> Result<Post> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::auth::require_admin;
+        use crate::ssr::server_utils::record_id;
+
+        require_admin().await?;
+        let id = record_id("post", &id)?;
+
+        let db = db().await;
+        let mut query = db
+            .query(
+                "SELECT *, author.*, <string>created_at AS created_at, <string>updated_at AS updated_at, <string>(edited_at ?? created_at) AS edited_at FROM ONLY $id;",
+            )
+            .bind(("id", id))
+            .await?;
+
+        Ok(query.take::<Option<Post>>(0)?.or_not_found("post not found")?)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[post("/api/admin/preview")]
+pub async fn admin_preview(markdown: String) -> Result<RenderedMarkdown> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::auth::require_admin;
+        use crate::ssr::server_utils::process_markdown;
+
+        require_admin().await?;
+
+        process_markdown(markdown).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+/// Creates a post and returns its record key.
+#[post("/api/admin/posts")]
+pub async fn admin_create_post(input: PostInput) -> Result<String> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::auth::require_admin;
+        use crate::ssr::server_utils::{process_markdown, record_id};
+        use surrealdb_types::{RecordId, ToSql};
+
+        require_admin().await?;
+        validate_post_input(&input)?;
+        let author = record_id("author", &input.author)?;
+        let rendered = process_markdown(input.body.clone()).await?;
+
+        let db = db().await;
+        let mut query = db
+            .query(
+                "CREATE post SET title = $title, summary = $summary, body = $body, body_html = $body_html, toc = $toc, tags = $tags, author = $author,
+                    is_published = $is_published, show_cta = $show_cta, header_image = $header_image,
+                    publish_at = $publish_at
+                RETURN VALUE id;",
+            )
+            .bind(("title", input.title.trim().to_string()))
+            .bind(("summary", input.summary.trim().to_string()))
+            .bind(("body", input.body))
+            .bind(("body_html", rendered.html))
+            .bind(("toc", rendered.toc))
+            .bind(("tags", input.tags))
+            .bind(("author", author))
+            .bind(("is_published", input.is_published))
+            .bind(("show_cta", input.show_cta))
+            .bind(("header_image", input.header_image))
+            .bind(("publish_at", input.publish_at))
+            .await?;
+
+        let id = query
+            .take::<Option<RecordId>>(0)?
+            .or_internal_server_error("post was not created")?;
+
+        Ok(id.key.to_sql())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[put("/api/admin/posts/{id}")]
+pub async fn admin_update_post(id: String, input: PostInput) -> Result<()> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::auth::require_admin;
+        use crate::ssr::server_utils::{process_markdown, record_id};
+        use surrealdb_types::RecordId;
+
+        require_admin().await?;
+        validate_post_input(&input)?;
+        let id = record_id("post", &id)?;
+        let author = record_id("author", &input.author)?;
+        let rendered = process_markdown(input.body.clone()).await?;
+
+        let db = db().await;
+        let mut query = db
+            .query(
+                "UPDATE $id SET title = $title, summary = $summary, body = $body, body_html = $body_html, toc = $toc, tags = $tags, author = $author,
+                    is_published = $is_published, show_cta = $show_cta, header_image = $header_image,
+                    publish_at = $publish_at, edited_at = time::now()
+                RETURN VALUE id;",
+            )
+            .bind(("id", id))
+            .bind(("title", input.title.trim().to_string()))
+            .bind(("summary", input.summary.trim().to_string()))
+            .bind(("body", input.body))
+            .bind(("body_html", rendered.html))
+            .bind(("toc", rendered.toc))
+            .bind(("tags", input.tags))
+            .bind(("author", author))
+            .bind(("is_published", input.is_published))
+            .bind(("show_cta", input.show_cta))
+            .bind(("header_image", input.header_image))
+            .bind(("publish_at", input.publish_at))
+            .await?;
+
+        query
+            .take::<Vec<RecordId>>(0)?
+            .into_iter()
+            .next()
+            .or_not_found("post not found")?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[delete("/api/admin/posts/{id}")]
+pub async fn admin_delete_post(id: String) -> Result<()> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::auth::require_admin;
+        use crate::ssr::server_utils::record_id;
+        use surrealdb_types::RecordId;
+
+        require_admin().await?;
+        let id = record_id("post", &id)?;
+
+        let db = db().await;
+        let mut query = db.query("DELETE $id RETURN BEFORE;").bind(("id", id)).await?;
+        query
+            .take::<Vec<RecordId>>((0, "id"))?
+            .into_iter()
+            .next()
+            .or_not_found("post not found")?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[get("/api/admin/analytics?days")]
+pub async fn admin_analytics(days: usize) -> Result<AnalyticsReport> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::auth::require_admin;
+
+        require_admin().await?;
+
+        crate::ssr::analytics::report(days.clamp(7, 365)).await
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[get("/api/admin/comments")]
+pub async fn admin_select_pending_comments() -> Result<Vec<PendingComment>> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::auth::require_admin;
+        use crate::ssr::comments::format_comment_date;
+
+        require_admin().await?;
+
+        let db = db().await;
+        let mut query = db
+            .query(
+                "
+        SELECT id, author_name, email, body_html, <string>created_at AS created_at, created_at AS sort,
+            post.title AS post_title, post.slug AS post_slug, parent != NONE AS is_reply
+            FROM comment WHERE status = 'pending' ORDER BY sort;
+        ",
+            )
+            .await?;
+        let mut comments = query.take::<Vec<PendingComment>>(0)?;
+        for comment in comments.iter_mut() {
+            comment.created_at = format_comment_date(&comment.created_at);
+        }
+
+        Ok(comments)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[put("/api/admin/comments/{id}")]
+pub async fn admin_approve_comment(id: String) -> Result<()> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::auth::require_admin;
+        use crate::ssr::server_utils::record_id;
+        use surrealdb_types::RecordId;
+
+        require_admin().await?;
+        let id = record_id("comment", &id)?;
+
+        let db = db().await;
+        let mut query = db
+            .query(
+                "UPDATE $id SET status = 'approved', approved_at = time::now() WHERE status = 'pending' RETURN BEFORE;",
+            )
+            .bind(("id", id))
+            .await?;
+        query
+            .take::<Vec<RecordId>>((0, "id"))?
+            .into_iter()
+            .next()
+            .or_not_found("comment not found")?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+/// Rejecting a comment deletes it, along with any replies.
+#[delete("/api/admin/comments/{id}")]
+pub async fn admin_delete_comment(id: String) -> Result<()> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::auth::require_admin;
+        use crate::ssr::server_utils::record_id;
+        use surrealdb_types::RecordId;
+
+        require_admin().await?;
+        let id = record_id("comment", &id)?;
+
+        let db = db().await;
+        let mut query = db.query("DELETE $id RETURN BEFORE;").bind(("id", id)).await?;
+        query
+            .take::<Vec<RecordId>>((0, "id"))?
+            .into_iter()
+            .next()
+            .or_not_found("comment not found")?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
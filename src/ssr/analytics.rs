@@ -0,0 +1,327 @@
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::sync::LazyLock;
+
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::header::{ACCEPT, HOST, REFERER, USER_AGENT};
+use axum::http::{HeaderMap, Method, Request, Response};
+use axum::middleware::Next;
+use chrono::{Duration, Utc};
+use dioxus::prelude::Result;
+use regex::Regex;
+use surrealdb_types::SurrealValue;
+
+use crate::app::Route;
+use crate::ssr::app_state::db;
+use crate::ssr::server_utils::client_ip;
+use crate::ssr::types::{AnalyticsReport, DailyCount, RankedCount};
+use crate::ssr::views::{is_bot, visitor_id};
+
+const TOP_LIMIT: usize = 10;
+
+/// Feed aggregators such as Feedly and Inoreader put their subscriber count
+/// in the User-Agent, e.g. `Feedly/1.0 (...; 42 subscribers)`.
+static RE_FEED_SUBSCRIBERS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d+)\s+(?:subscribers|readers)").expect("valid regex pattern"));
+
+enum Hit {
+    Page { path: String, referrer: Option<String> },
+    Feed { path: String, subscribers: usize },
+}
+
+/// Full page loads are HTML documents. Client-side navigation to a post
+/// never requests the page itself, only `/api/post/{slug}`, so that fetch
+/// counts as a view of `/post/{slug}`. Paths that only match the not-found
+/// route are skipped.
+fn classify(method: &Method, path: &str, headers: &HeaderMap) -> Option<Hit> {
+    if method != Method::GET {
+        return None;
+    }
+
+    let header = |name| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+    };
+    let user_agent = header(USER_AGENT);
+
+    let scoped_feed = path.starts_with("/tag/") || path.starts_with("/author/");
+    if matches!(path, "/rss.xml" | "/atom.xml" | "/feed.json") || (scoped_feed && path.ends_with("/rss.xml")) {
+        if user_agent.is_empty() {
+            return None;
+        }
+        let subscribers = RE_FEED_SUBSCRIBERS
+            .captures(user_agent)
+            .and_then(|captures| captures[1].parse().ok())
+            .unwrap_or(1);
+        return Some(Hit::Feed {
+            path: path.to_string(),
+            subscribers,
+        });
+    }
+
+    if is_bot(user_agent) {
+        return None;
+    }
+
+    let page = match path.strip_prefix("/api/post/") {
+        Some(slug) if !slug.is_empty() && !slug.contains('/') => format!("/post/{slug}"),
+        Some(_) => return None,
+        None if path.starts_with("/api/") || path.starts_with("/admin") || path.starts_with("/newsletter") => {
+            return None;
+        }
+        None if path.rsplit('/').next().is_some_and(|segment| segment.contains('.')) => return None,
+        None if header(ACCEPT).contains("text/html") => path.to_string(),
+        None => return None,
+    };
+    if !matches!(page.parse::<Route>(), Ok(route) if !matches!(route, Route::PageNotFound { .. })) {
+        return None;
+    }
+
+    Some(Hit::Page {
+        path: page,
+        referrer: external_referrer(header(REFERER), header(HOST)),
+    })
+}
+
+/// The referring host, without `www.`, unless it's this site.
+fn external_referrer(referer: &str, host: &str) -> Option<String> {
+    let (_, rest) = referer.split_once("://")?;
+    let referrer = rest.split(['/', '?', '#']).next()?.split(':').next()?.to_lowercase();
+    let referrer = referrer.trim_start_matches("www.");
+    let host = host.split(':').next().unwrap_or_default().to_lowercase();
+
+    if referrer.is_empty() || referrer == host.trim_start_matches("www.") {
+        return None;
+    }
+    Some(referrer.to_string())
+}
+
+async fn record(hit: Hit, ip: String, user_agent: String) -> Result<()> {
+    let (day, visitor) = visitor_id(&ip, &user_agent).await?;
+    let db = db().await;
+
+    match hit {
+        Hit::Page { path, referrer } => {
+            db.query(
+                "INSERT IGNORE INTO page_view { id: [$visitor, $path, $day], path: $path, referrer: $referrer, visitor: $visitor, day: $day };",
+            )
+                .bind(("path", path))
+                .bind(("referrer", referrer))
+                .bind(("visitor", visitor))
+                .bind(("day", day))
+                .await?
+                .check()?;
+        }
+        Hit::Feed { path, subscribers } => {
+            db.query(
+                "INSERT IGNORE INTO feed_fetch { id: [$visitor, $path, $day], feed: $path, visitor: $visitor, day: $day, subscribers: $subscribers };",
+            )
+            .bind(("path", path))
+            .bind(("visitor", visitor))
+            .bind(("day", day))
+            .bind(("subscribers", subscribers))
+            .await?
+            .check()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Records successful page loads and feed fetches without cookies. Writes
+/// happen in the background so they never slow down the response.
+pub async fn record_page_view(req: Request<Body>, next: Next) -> Response<Body> {
+    let hit = classify(req.method(), req.uri().path(), req.headers());
+    let peer = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let ip = client_ip(req.headers(), peer).unwrap_or("unknown".to_string());
+    let user_agent = req
+        .headers()
+        .get(USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+
+    let response = next.run(req).await;
+
+    if let Some(hit) = hit.filter(|_| response.status().is_success()) {
+        tokio::spawn(async move {
+            if let Err(err) = record(hit, ip, user_agent).await {
+                tracing::warn!("failed to record page view: {err}");
+            }
+        });
+    }
+
+    response
+}
+
+#[derive(SurrealValue)]
+struct DayCount {
+    day: String,
+    count: usize,
+}
+
+#[derive(SurrealValue)]
+struct DayVisitor {
+    day: String,
+}
+
+#[derive(SurrealValue)]
+struct PathCount {
+    path: String,
+    count: usize,
+}
+
+#[derive(SurrealValue)]
+struct ReferrerCount {
+    referrer: String,
+    count: usize,
+}
+
+#[derive(SurrealValue)]
+struct PostTitle {
+    slug: String,
+    title: String,
+}
+
+fn zero_filled(days: &[String], counts: impl IntoIterator<Item = (String, usize)>) -> Vec<DailyCount> {
+    let counts = counts.into_iter().collect::<HashMap<_, _>>();
+    days.iter()
+        .map(|day| DailyCount {
+            day: day.clone(),
+            count: counts.get(day).copied().unwrap_or(0),
+        })
+        .collect()
+}
+
+pub async fn report(days: usize) -> Result<AnalyticsReport> {
+    let today = Utc::now().date_naive();
+    let window = (0..days)
+        .rev()
+        .map(|offset| (today - Duration::days(offset as i64)).to_string())
+        .collect::<Vec<_>>();
+    let since = window.first().cloned().unwrap_or_else(|| today.to_string());
+
+    let db = db().await;
+    let mut query = db
+        .query(
+            "
+        SELECT day, count() AS count FROM page_view WHERE day >= $since GROUP BY day;
+        SELECT day, visitor FROM page_view WHERE day >= $since GROUP BY day, visitor;
+        SELECT path, count() AS count FROM page_view WHERE day >= $since AND string::starts_with(path, '/post/') GROUP BY path;
+        SELECT referrer, count() AS count FROM page_view WHERE day >= $since AND referrer != NONE GROUP BY referrer;
+        SELECT day, math::sum(subscribers) AS count FROM feed_fetch WHERE day >= $since GROUP BY day;
+        ",
+        )
+        .bind(("since", since))
+        .await?;
+
+    let views = query.take::<Vec<DayCount>>(0)?;
+    let visitors = query.take::<Vec<DayVisitor>>(1)?;
+    let mut top_posts = query.take::<Vec<PathCount>>(2)?;
+    let mut top_referrers = query.take::<Vec<ReferrerCount>>(3)?;
+    let feed_subscribers = query.take::<Vec<DayCount>>(4)?;
+
+    top_posts.sort_by_key(|post| std::cmp::Reverse(post.count));
+    top_posts.truncate(TOP_LIMIT);
+    top_referrers.sort_by_key(|referrer| std::cmp::Reverse(referrer.count));
+    top_referrers.truncate(TOP_LIMIT);
+
+    let slugs = top_posts
+        .iter()
+        .filter_map(|post| post.path.strip_prefix("/post/").map(str::to_string))
+        .collect::<Vec<_>>();
+    let mut query = db
+        .query("SELECT slug, title FROM post WHERE slug IN $slugs;")
+        .bind(("slugs", slugs))
+        .await?;
+    let titles = query
+        .take::<Vec<PostTitle>>(0)?
+        .into_iter()
+        .map(|post| (post.slug, post.title))
+        .collect::<HashMap<_, _>>();
+
+    // Grouping by day and visitor returns one row per distinct visitor.
+    let mut visitors_per_day = BTreeMap::<String, usize>::new();
+    for row in visitors {
+        *visitors_per_day.entry(row.day).or_default() += 1;
+    }
+
+    Ok(AnalyticsReport {
+        days,
+        views: zero_filled(&window, views.into_iter().map(|row| (row.day, row.count))),
+        visitors: zero_filled(&window, visitors_per_day),
+        top_posts: top_posts
+            .into_iter()
+            .map(|post| {
+                let label = post
+                    .path
+                    .strip_prefix("/post/")
+                    .and_then(|slug| titles.get(slug).cloned())
+                    .unwrap_or_else(|| post.path.clone());
+                RankedCount {
+                    label,
+                    path: Some(post.path),
+                    count: post.count,
+                }
+            })
+            .collect(),
+        top_referrers: top_referrers
+            .into_iter()
+            .map(|referrer| RankedCount {
+                label: referrer.referrer,
+                path: None,
+                count: referrer.count,
+            })
+            .collect(),
+        feed_subscribers: zero_filled(&window, feed_subscribers.into_iter().map(|row| (row.day, row.count))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn html_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, "text/html,application/xhtml+xml".parse().unwrap());
+        headers.insert(
+            USER_AGENT,
+            "Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0".parse().unwrap(),
+        );
+        headers
+    }
+
+    fn page_path(path: &str) -> Option<String> {
+        match classify(&Method::GET, path, &html_headers())? {
+            Hit::Page { path, .. } => Some(path),
+            Hit::Feed { .. } => None,
+        }
+    }
+
+    #[test]
+    fn routed_pages_are_recorded() {
+        assert_eq!(page_path("/").as_deref(), Some("/"));
+        assert_eq!(page_path("/tag/rust").as_deref(), Some("/tag/rust"));
+        assert_eq!(page_path("/api/post/async-rust").as_deref(), Some("/post/async-rust"));
+    }
+
+    #[test]
+    fn unrouted_paths_are_not_recorded() {
+        for path in [
+            "/wp-login.php",
+            "/random/3f9a1c",
+            "/post/a/b",
+            "/api/posts",
+            "/admin",
+            "/favicon.ico",
+        ] {
+            assert_eq!(page_path(path), None, "{path}");
+        }
+    }
+}
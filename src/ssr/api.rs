@@ -1,110 +1,50 @@
 use std::collections::BTreeMap;
 
-use crate::ssr::types::Post;
+use crate::ssr::types::{
+    AuthorPage, OssProject, Post, PostPage, Project, ProjectPage, Reference, RelatedPost, RepoStats, SearchResult,
+};
 use dioxus::prelude::*;
 
 #[cfg(feature = "server")]
-use serde::Deserialize;
+const TAG_PAGE_SIZE: usize = 10;
 
 #[cfg(feature = "server")]
-use std::time::{Duration, Instant};
+const SEARCH_RESULT_LIMIT: usize = 20;
 
 #[cfg(feature = "server")]
-use tokio::sync::RwLock;
+const RELATED_POSTS_MAX: usize = 12;
 
+/// Age at which a related post's recency bonus has halved. Old posts never
+/// drop below half their tag-overlap score, so a strong match still shows.
 #[cfg(feature = "server")]
-const REPO_STARS_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+const RELATED_HALF_LIFE_DAYS: f64 = 365.0;
 
-#[cfg(feature = "server")]
-static REPO_STARS_CACHE: RwLock<Option<RepoStarsCache>> = RwLock::const_new(None);
-
-#[cfg(feature = "server")]
-#[derive(Clone)]
-struct RepoStarsCache {
-    fetched_at: Instant,
-    stars: BTreeMap<String, u32>,
-}
-
-#[cfg(feature = "server")]
-#[derive(Deserialize)]
-struct GithubRepo {
-    stargazers_count: u32,
-}
-
-#[cfg(feature = "server")]
-async fn fetch_repo_stars_from_github() -> BTreeMap<String, u32> {
-    use std::env;
-
-    use crate::pages::opensource::PROJECTS;
-    use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT};
+/// Visible open-source projects in their configured order.
+#[get("/api/opensource/projects")]
+pub async fn select_oss_projects() -> Result<Vec<OssProject>> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
 
-    let mut headers = HeaderMap::new();
-    headers.insert(ACCEPT, HeaderValue::from_static("application/vnd.github+json"));
-    headers.insert(USER_AGENT, HeaderValue::from_static("rust-dd-blog"));
-    headers.insert("X-GitHub-Api-Version", HeaderValue::from_static("2022-11-28"));
+        let db = db().await;
+        let mut query = db
+            .query("SELECT * FROM oss_project WHERE is_visible = true ORDER BY position, name;")
+            .await?;
 
-    if let Ok(token) = env::var("GITHUB_TOKEN") {
-        if let Ok(value) = HeaderValue::from_str(&format!("Bearer {token}")) {
-            headers.insert(AUTHORIZATION, value);
-        }
+        Ok(query.take::<Vec<OssProject>>(0)?)
     }
-
-    let client = match reqwest::Client::builder().default_headers(headers).build() {
-        Ok(client) => client,
-        Err(_) => return BTreeMap::new(),
-    };
-
-    let mut stars = BTreeMap::new();
-
-    for project in PROJECTS.iter() {
-        let Some((owner, repo)) = project.github_repo.split_once('/') else {
-            continue;
-        };
-
-        let response = match client
-            .get(format!("https://api.github.com/repos/{owner}/{repo}"))
-            .send()
-            .await
-        {
-            Ok(response) if response.status().is_success() => response,
-            _ => continue,
-        };
-
-        let payload = match response.json::<GithubRepo>().await {
-            Ok(payload) => payload,
-            Err(_) => continue,
-        };
-
-        stars.insert(project.github_repo.to_string(), payload.stargazers_count);
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
     }
-
-    stars
 }
 
+/// GitHub stats per `owner/repo` from the latest stored snapshots.
 #[get("/api/github/stars")]
-pub async fn select_repo_stars() -> Result<BTreeMap<String, u32>> {
+pub async fn select_repo_stars() -> Result<BTreeMap<String, RepoStats>> {
     #[cfg(feature = "server")]
     {
-        let now = Instant::now();
-
-        {
-            let cache = REPO_STARS_CACHE.read().await;
-            if let Some(cache) = cache.as_ref() {
-                if now.duration_since(cache.fetched_at) < REPO_STARS_CACHE_TTL {
-                    return Ok(cache.stars.clone());
-                }
-            }
-        }
-
-        let stars = fetch_repo_stars_from_github().await;
-
-        let mut cache = REPO_STARS_CACHE.write().await;
-        *cache = Some(RepoStarsCache {
-            fetched_at: now,
-            stars: stars.clone(),
-        });
-
-        Ok(stars)
+        Ok(crate::ssr::github::repo_stats().await?)
     }
     #[cfg(not(feature = "server"))]
     {
@@ -121,7 +61,7 @@ pub async fn select_posts() -> Result<Vec<Post>> {
 
         let db = db().await;
         let mut query = db
-            .query("SELECT *, author.*, <string>created_at AS created_at, <string>updated_at AS updated_at from post WHERE is_published = true ORDER BY created_at DESC;")
+            .query("SELECT *, author.*, <string>created_at AS created_at, <string>updated_at AS updated_at, <string>(edited_at ?? created_at) AS edited_at from post WHERE fn::post::is_live(is_published, publish_at) ORDER BY created_at DESC;")
             .await?;
 
         let mut posts = query.take::<Vec<Post>>(0)?;
@@ -152,16 +92,21 @@ pub async fn select_tags() -> Result<BTreeMap<String, usize>> {
         let mut query = db
             .query(
                 "
-        LET $tags = SELECT tags FROM post;
+        LET $tags = SELECT tags FROM post WHERE fn::post::is_live(is_published, publish_at);
         array::flatten($tags.map(|$t| $t.tags));
         ",
             )
             .await?;
 
+        // Keyed the way tag pages look tags up, so `Rust` and `rust` are one
+        // entry linking to one page.
         let tags = query.take::<Vec<String>>(1)?;
         let mut tag_map = BTreeMap::<String, usize>::new();
         for tag in tags {
-            *tag_map.entry(tag).or_insert(0) += 1;
+            let tag = tag.trim().to_lowercase();
+            if !tag.is_empty() {
+                *tag_map.entry(tag).or_insert(0) += 1;
+            }
         }
 
         Ok(tag_map)
@@ -172,31 +117,396 @@ pub async fn select_tags() -> Result<BTreeMap<String, usize>> {
     }
 }
 
+#[get("/api/tag/{tag}?page")]
+pub async fn select_posts_by_tag(tag: String, page: usize) -> Result<PostPage> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::server_utils::normalize_tag;
+        use chrono::{DateTime, Utc};
+
+        let tag = normalize_tag(&tag)?;
+        let page = page.max(1);
+
+        let db = db().await;
+        let mut query = db
+            .query(
+                "
+        SELECT *, author.*, <string>created_at AS created_at, <string>updated_at AS updated_at, <string>(edited_at ?? created_at) AS edited_at FROM post
+            WHERE fn::post::is_live(is_published, publish_at) AND tags.map(|$t| string::lowercase(string::trim($t))) CONTAINS $tag
+            ORDER BY created_at DESC LIMIT $limit START $start;
+        SELECT count() FROM post
+            WHERE fn::post::is_live(is_published, publish_at) AND tags.map(|$t| string::lowercase(string::trim($t))) CONTAINS $tag
+            GROUP ALL;
+        ",
+            )
+            .bind(("tag", tag))
+            .bind(("limit", TAG_PAGE_SIZE))
+            .bind(("start", (page - 1) * TAG_PAGE_SIZE))
+            .await?;
+
+        let mut posts = query.take::<Vec<Post>>(0)?;
+        let total_posts = query.take::<Option<usize>>((1, "count"))?.unwrap_or(0);
+        if total_posts == 0 {
+            return HttpError::not_found("tag not found")?;
+        }
+
+        posts.iter_mut().for_each(|post| {
+            let date_time = DateTime::parse_from_rfc3339(&post.created_at)
+                .unwrap()
+                .with_timezone(&Utc);
+            let naive_date = date_time.date_naive();
+            let formatted_date = naive_date.format("%b %-d, %Y").to_string();
+            post.created_at = formatted_date;
+        });
+
+        Ok(PostPage {
+            posts,
+            page,
+            total_pages: total_posts.div_ceil(TAG_PAGE_SIZE),
+            total_posts,
+        })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[get("/api/author/{id}")]
+pub async fn select_author(id: String) -> Result<AuthorPage> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::server_utils::record_id;
+        use crate::ssr::types::Author;
+        use chrono::{DateTime, Utc};
+
+        let id = record_id("author", &id)?;
+
+        let db = db().await;
+        let mut query = db
+            .query(
+                "
+        SELECT * FROM $id;
+        SELECT *, author.*, <string>created_at AS created_at, <string>updated_at AS updated_at, <string>(edited_at ?? created_at) AS edited_at FROM post
+            WHERE fn::post::is_live(is_published, publish_at) AND author = $id
+            ORDER BY created_at DESC;
+        ",
+            )
+            .bind(("id", id))
+            .await?;
+
+        let author = query
+            .take::<Vec<Author>>(0)?
+            .into_iter()
+            .next()
+            .or_not_found("author not found")?;
+        let mut posts = query.take::<Vec<Post>>(1)?;
+
+        posts.iter_mut().for_each(|post| {
+            let date_time = DateTime::parse_from_rfc3339(&post.created_at)
+                .unwrap()
+                .with_timezone(&Utc);
+            post.created_at = date_time.date_naive().format("%b %-d, %Y").to_string();
+        });
+
+        Ok(AuthorPage { author, posts })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+/// Published client references, newest year first. The page filters them
+/// by category and year itself, so it can list every option.
+#[get("/api/references")]
+pub async fn select_references() -> Result<Vec<Reference>> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+
+        let db = db().await;
+        let mut query = db
+            .query("SELECT *, <string>created_at AS created_at, <string>updated_at AS updated_at FROM reference WHERE is_published = true ORDER BY year DESC, created_at DESC;")
+            .await?;
+
+        Ok(query.take::<Vec<Reference>>(0)?)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[get("/api/reference/{id}")]
+pub async fn select_reference(id: String) -> Result<Reference> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::server_utils::record_id;
+
+        let id = record_id("reference", &id)?;
+
+        let db = db().await;
+        let mut query = db
+            .query("SELECT *, <string>created_at AS created_at, <string>updated_at AS updated_at FROM $id WHERE is_published = true;")
+            .bind(("id", id))
+            .await?;
+
+        Ok(query
+            .take::<Vec<Reference>>(0)?
+            .into_iter()
+            .next()
+            .or_not_found("reference not found")?)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+/// Visible projects in their configured order, bodies left as markdown.
+#[get("/api/projects")]
+pub async fn select_projects() -> Result<Vec<Project>> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+
+        let db = db().await;
+        let mut query = db
+            .query("SELECT * FROM project WHERE is_visible = true ORDER BY position, name;")
+            .await?;
+
+        Ok(query.take::<Vec<Project>>(0)?)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[get("/api/project/{slug}")]
+pub async fn select_project(slug: String) -> Result<ProjectPage> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::server_utils::{process_markdown, validate_slug};
+        use crate::ssr::types::PostLink;
+
+        validate_slug(&slug)?;
+
+        let db = db().await;
+        let mut query = db
+            .query("SELECT * FROM project WHERE slug = $slug AND is_visible = true;")
+            .bind(("slug", slug))
+            .await?;
+        let mut project = query
+            .take::<Vec<Project>>(0)?
+            .into_iter()
+            .next()
+            .or_not_found("project not found")?;
+
+        let mut query = db
+            .query("SELECT slug, title, summary FROM post WHERE slug IN $slugs AND fn::post::is_live(is_published, publish_at);")
+            .bind(("slugs", project.related_posts.clone()))
+            .await?;
+        let mut related_posts = query.take::<Vec<PostLink>>(0)?;
+        related_posts.sort_by_key(|post| {
+            project
+                .related_posts
+                .iter()
+                .position(|slug| post.slug.as_ref() == Some(slug))
+        });
+
+        project.body = process_markdown(project.body).await?.html;
+
+        Ok(ProjectPage { project, related_posts })
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[get("/api/search?query")]
+pub async fn search_posts(query: String) -> Result<Vec<SearchResult>> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::server_utils::{highlight_html, highlight_snippet, HIGHLIGHT_END, HIGHLIGHT_START};
+        use chrono::{DateTime, Utc};
+        use surrealdb_types::SurrealValue;
+
+        #[derive(SurrealValue)]
+        struct SearchRow {
+            slug: Option<String>,
+            title: String,
+            summary: String,
+            body: String,
+            tags: Vec<String>,
+            read_time: usize,
+            created_at: String,
+            score: f64,
+        }
+
+        let query = query.trim().to_string();
+        if query.is_empty() {
+            return Ok(vec![]);
+        }
+        if query.chars().count() > 100 || query.chars().any(char::is_control) {
+            return HttpError::bad_request("invalid search query")?;
+        }
+
+        // Title and tag matches outrank summary matches, which outrank body
+        // matches; `?? 0` covers the fields that didn't match at all.
+        let db = db().await;
+        let mut response = db
+            .query(
+                "
+        SELECT slug, tags, read_time, <string>created_at AS created_at,
+            search::highlight($start, $end, 0) AS title,
+            search::highlight($start, $end, 1) AS summary,
+            search::highlight($start, $end, 2) AS body,
+            (search::score(0) ?? 0) * 4 + (search::score(3) ?? 0) * 3 + (search::score(1) ?? 0) * 2 + (search::score(2) ?? 0) AS score
+        FROM post
+        WHERE fn::post::is_live(is_published, publish_at)
+            AND (title @0@ $query OR summary @1@ $query OR body @2@ $query OR tags @3@ $query)
+        ORDER BY score DESC
+        LIMIT $limit;
+        ",
+            )
+            .bind(("query", query))
+            .bind(("start", HIGHLIGHT_START))
+            .bind(("end", HIGHLIGHT_END))
+            .bind(("limit", SEARCH_RESULT_LIMIT))
+            .await?;
+
+        let rows = response.take::<Vec<SearchRow>>(0)?;
+        let results = rows
+            .into_iter()
+            .map(|row| {
+                let snippet = if row.body.contains(HIGHLIGHT_START) {
+                    highlight_snippet(&row.body)
+                } else {
+                    highlight_snippet(&row.summary)
+                };
+                let created_at = DateTime::parse_from_rfc3339(&row.created_at)
+                    .map(|date_time| date_time.with_timezone(&Utc).format("%b %-d, %Y").to_string())
+                    .unwrap_or_default();
+
+                SearchResult {
+                    slug: row.slug,
+                    title: highlight_html(&row.title),
+                    snippet,
+                    tags: row.tags,
+                    created_at,
+                    read_time: row.read_time,
+                    score: row.score,
+                }
+            })
+            .collect();
+
+        Ok(results)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+/// Live posts sharing at least one tag with `slug`, best first. Each shared
+/// tag counts one point, scaled by a recency factor between 0.5 and 1.
+#[get("/api/post/{slug}/related?limit")]
+pub async fn select_related_posts(slug: String, limit: usize) -> Result<Vec<RelatedPost>> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::server_utils::validate_slug;
+        use chrono::{DateTime, Utc};
+
+        validate_slug(&slug)?;
+        let limit = limit.clamp(1, RELATED_POSTS_MAX);
+
+        let db = db().await;
+        let mut query = db
+            .query(
+                "
+        LET $source = (SELECT id, tags FROM post WHERE slug = $slug AND fn::post::is_live(is_published, publish_at) LIMIT 1)[0];
+        $source.tags.map(|$t| string::lowercase(string::trim($t)));
+        SELECT id, slug, title, summary, tags, read_time, <string>created_at AS created_at FROM post
+            WHERE fn::post::is_live(is_published, publish_at) AND id != $source.id
+                AND tags.map(|$t| string::lowercase(string::trim($t))) CONTAINSANY $source.tags.map(|$t| string::lowercase(string::trim($t)));
+        ",
+            )
+            .bind(("slug", slug))
+            .await?;
+
+        let tags = query.take::<Option<Vec<String>>>(1)?.or_not_found("post not found")?;
+        let candidates = query.take::<Vec<RelatedPost>>(2)?;
+
+        let now = Utc::now();
+        let mut scored = candidates
+            .into_iter()
+            .filter_map(|mut post| {
+                let shared = post
+                    .tags
+                    .iter()
+                    .filter(|tag| tags.contains(&tag.trim().to_lowercase()))
+                    .count();
+                let created_at = DateTime::parse_from_rfc3339(&post.created_at).ok()?.with_timezone(&Utc);
+                let age_days = (now - created_at).num_days().max(0) as f64;
+                let recency = (1.0 + 0.5_f64.powf(age_days / RELATED_HALF_LIFE_DAYS)) / 2.0;
+
+                post.created_at = created_at.date_naive().format("%b %-d, %Y").to_string();
+                Some((shared as f64 * recency, post))
+            })
+            .collect::<Vec<_>>();
+        scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+
+        Ok(scored.into_iter().take(limit).map(|(_, post)| post).collect())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
 #[get("/api/post/{slug}")]
 pub async fn select_post(slug: String) -> Result<Post> {
     #[cfg(feature = "server")]
     {
         use crate::ssr::app_state::db;
-        use crate::ssr::server_utils::process_markdown;
+        use crate::ssr::server_utils::{render_post_body, validate_slug};
         use chrono::{DateTime, Utc};
 
+        validate_slug(&slug)?;
+
         let db = db().await;
         let mut query = db
-            .query(format!(
-                r#"SELECT *, author.*, <string>created_at AS created_at, <string>updated_at AS updated_at from post WHERE slug = "{slug}""#
-            ))
+            .query("SELECT *, author.*, <string>created_at AS created_at, <string>updated_at AS updated_at, <string>(edited_at ?? created_at) AS edited_at FROM post WHERE slug = $slug AND fn::post::is_live(is_published, publish_at);")
+            .bind(("slug", slug))
             .await?;
-        let post = query.take::<Vec<Post>>(0)?;
-        let mut post = match post.first().cloned() {
-            Some(post) => post,
-            None => return Err(std::io::Error::new(std::io::ErrorKind::NotFound, "post not found").into()),
-        };
+        let mut post = query
+            .take::<Vec<Post>>(0)?
+            .into_iter()
+            .next()
+            .or_not_found("post not found")?;
 
         let date_time = DateTime::parse_from_rfc3339(&post.created_at)?.with_timezone(&Utc);
+        post.published_at = Some(
+            post.publish_at
+                .as_ref()
+                .map_or(date_time, |publish_at| **publish_at)
+                .to_rfc3339(),
+        );
         let naive_date = date_time.date_naive();
         let formatted_date = naive_date.format("%b %-d").to_string();
         post.created_at = formatted_date;
-        post.body = process_markdown(post.body.clone()).await?;
+        let rendered = render_post_body(&post).await?;
+        post.body = rendered.html;
+        post.toc = Some(rendered.toc);
 
         Ok(post)
     }
@@ -211,10 +521,45 @@ pub async fn increment_views(id: String) -> Result<()> {
     #[cfg(feature = "server")]
     {
         use crate::ssr::app_state::db;
+        use crate::ssr::server_utils::{record_id, request_ip};
+        use crate::ssr::views::{is_bot, visitor_id};
+        use axum::http::header::USER_AGENT;
+        use axum::http::HeaderMap;
+        use dioxus::fullstack::FullstackContext;
+        use surrealdb_types::RecordId;
+
+        let id = record_id("post", &id)?;
+
+        let headers = FullstackContext::extract::<HeaderMap, _>().await.unwrap_or_default();
+        let user_agent = headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        if is_bot(user_agent) {
+            return Ok(());
+        }
+        let ip = request_ip().await.unwrap_or("unknown".to_string());
+        let (day, visitor) = visitor_id(&ip, user_agent).await?;
 
+        // The `post_view_count` event bumps `total_views` only when the
+        // insert actually creates a row, i.e. on the visitor's first view
+        // of the post that day.
         let db = db().await;
-        db.query(format!("UPDATE post:{0} SET total_views = total_views + 1;", id))
+        let mut query = db
+            .query(
+                "
+        LET $live = (SELECT VALUE id FROM $id WHERE fn::post::is_live(is_published, publish_at))[0];
+        IF $live != NONE {
+            INSERT IGNORE INTO post_view { id: [$live, $visitor, $day], post: $live, visitor: $visitor, day: $day };
+        };
+        RETURN $live;
+        ",
+            )
+            .bind(("id", id))
+            .bind(("visitor", visitor))
+            .bind(("day", day))
             .await?;
+        query.take::<Option<RecordId>>(2)?.or_not_found("post not found")?;
 
         Ok(())
     }
@@ -223,3 +568,90 @@ pub async fn increment_views(id: String) -> Result<()> {
         unreachable!()
     }
 }
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    use crate::ssr::comments::{select_comments, submit_comment};
+    use crate::ssr::types::CommentInput;
+
+    /// Slugs and record keys that must never reach a query.
+    const ID_PAYLOADS: &[&str] = &[
+        "' OR 1=1 --",
+        "x; DELETE post;",
+        "post:x",
+        "x⟩; REMOVE TABLE post; --",
+        "../admin",
+        "",
+    ];
+
+    /// Free text is bound as a parameter, so quotes and keywords are harmless
+    /// there; these are the payloads its validation turns away.
+    const TEXT_PAYLOADS: &[&str] = &[
+        "rust\0' OR 1=1 --",
+        "rust\n; DELETE post;",
+        "\u{1b}[2J; REMOVE TABLE post",
+    ];
+
+    /// Every payload here is rejected before a database connection is even
+    /// opened, so these run without SurrealDB.
+    #[tokio::test]
+    async fn endpoints_reject_injection_payloads() {
+        let comment = CommentInput {
+            author_name: "Jane".to_string(),
+            body: "Nice post.".to_string(),
+            ..CommentInput::default()
+        };
+
+        for payload in ID_PAYLOADS.iter().map(|payload| payload.to_string()) {
+            assert!(
+                select_post(payload.clone()).await.is_err(),
+                "select_post accepted {payload:?}"
+            );
+            assert!(
+                increment_views(payload.clone()).await.is_err(),
+                "increment_views accepted {payload:?}"
+            );
+            assert!(
+                select_author(payload.clone()).await.is_err(),
+                "select_author accepted {payload:?}"
+            );
+            assert!(
+                select_reference(payload.clone()).await.is_err(),
+                "select_reference accepted {payload:?}"
+            );
+            assert!(
+                select_project(payload.clone()).await.is_err(),
+                "select_project accepted {payload:?}"
+            );
+            assert!(
+                select_related_posts(payload.clone(), 3).await.is_err(),
+                "select_related_posts accepted {payload:?}"
+            );
+            assert!(
+                select_comments(payload.clone()).await.is_err(),
+                "select_comments accepted {payload:?}"
+            );
+            assert!(
+                submit_comment(payload.clone(), comment.clone()).await.is_err(),
+                "submit_comment accepted {payload:?}"
+            );
+        }
+
+        let overlong = format!("{}' OR 1=1 --", "x".repeat(100));
+        for payload in TEXT_PAYLOADS
+            .iter()
+            .map(|payload| payload.to_string())
+            .chain([overlong])
+        {
+            assert!(
+                select_posts_by_tag(payload.clone(), 1).await.is_err(),
+                "select_posts_by_tag accepted {payload:?}"
+            );
+            assert!(
+                search_posts(payload.clone()).await.is_err(),
+                "search_posts accepted {payload:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::env;
+use std::time::Duration;
+
+use axum::http::header::{COOKIE, SET_COOKIE};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use chrono::Utc;
+use dioxus::fullstack::FullstackContext;
+use dioxus::prelude::HttpError;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use surrealdb::engine::remote::http::{Http, Https};
+use surrealdb::opt::auth::Database;
+use surrealdb::Surreal;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const SESSION_COOKIE: &str = "blog_admin";
+const SESSION_TTL_SECS: i64 = 12 * 60 * 60;
+const FAILED_LOGIN_DELAY: Duration = Duration::from_secs(1);
+
+/// Usernames allowed into `/admin`, from `ADMIN_USERS` (comma separated).
+/// Every other database user, `blog_svc` included, is refused even with a
+/// valid password.
+fn admin_users() -> Vec<String> {
+    env::var("ADMIN_USERS")
+        .unwrap_or("blog_admin".to_string())
+        .split(',')
+        .map(|user| user.trim().to_string())
+        .filter(|user| !user.is_empty())
+        .collect()
+}
+
+fn session_secret() -> Result<Vec<u8>, HttpError> {
+    match env::var("ADMIN_SESSION_SECRET") {
+        Ok(secret) if secret.len() >= 32 => Ok(secret.into_bytes()),
+        _ => Err(HttpError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "admin is not configured",
+        )),
+    }
+}
+
+pub(crate) fn sign(secret: &[u8], payload: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("hmac accepts any key length");
+    mac.update(payload.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+pub(crate) fn verify(secret: &[u8], payload: &str, signature: &str) -> bool {
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+    let mut mac = HmacSha256::new_from_slice(secret).expect("hmac accepts any key length");
+    mac.update(payload.as_bytes());
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// Checks the credentials against the SurrealDB database user of the same
+/// name, so passwords are stored and hashed by the database, not the app.
+pub async fn verify_credentials(username: &str, password: &str) -> Result<(), HttpError> {
+    if !admin_users().iter().any(|user| user == username) {
+        tokio::time::sleep(FAILED_LOGIN_DELAY).await;
+        return HttpError::unauthorized("invalid credentials");
+    }
+
+    let protocol = env::var("SURREAL_PROTOCOL").unwrap_or("http".to_string());
+    let host = env::var("SURREAL_HOST").unwrap_or("127.0.0.1:8000".to_string());
+    let ns = env::var("SURREAL_NS").unwrap_or("rustblog".to_string());
+    let db_name = env::var("SURREAL_DB").unwrap_or("rustblog".to_string());
+
+    let db = if protocol == "http" {
+        Surreal::new::<Http>(host).await
+    } else {
+        Surreal::new::<Https>(host).await
+    }
+    .or_else(|_| HttpError::internal_server_error("database unavailable"))?;
+
+    let signin = db
+        .signin(Database {
+            namespace: ns,
+            database: db_name,
+            username: username.to_string(),
+            password: password.to_string(),
+        })
+        .await;
+
+    match signin {
+        Ok(_) => Ok(()),
+        Err(_) => {
+            tokio::time::sleep(FAILED_LOGIN_DELAY).await;
+            HttpError::unauthorized("invalid credentials")
+        }
+    }
+}
+
+/// Session tokens are `{username}.{expires_at}.{hmac}`; nothing is stored
+/// server-side, so rotating `ADMIN_SESSION_SECRET` logs every editor out.
+pub fn issue_session(username: &str) -> Result<(), HttpError> {
+    let secret = session_secret()?;
+    let expires_at = Utc::now().timestamp() + SESSION_TTL_SECS;
+    let payload = format!("{username}.{expires_at}");
+    let token = format!("{payload}.{}", sign(&secret, &payload));
+
+    set_session_cookie(&format!(
+        "{SESSION_COOKIE}={token}; Path=/; Max-Age={SESSION_TTL_SECS}; HttpOnly; Secure; SameSite=Strict"
+    ));
+    Ok(())
+}
+
+pub fn clear_session() {
+    set_session_cookie(&format!(
+        "{SESSION_COOKIE}=; Path=/; Max-Age=0; HttpOnly; Secure; SameSite=Strict"
+    ));
+}
+
+fn set_session_cookie(cookie: &str) {
+    if let (Some(context), Ok(value)) = (FullstackContext::current(), HeaderValue::from_str(cookie)) {
+        context.add_response_header(SET_COOKIE, value);
+    }
+}
+
+fn session_user(headers: &HeaderMap) -> Option<String> {
+    let secret = session_secret().ok()?;
+    let token = headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE)
+        .map(|(_, value)| value.to_string())?;
+
+    let (payload, signature) = token.rsplit_once('.')?;
+    if !verify(&secret, payload, signature) {
+        return None;
+    }
+
+    let (username, expires_at) = payload.rsplit_once('.')?;
+    let expires_at = expires_at.parse::<i64>().ok()?;
+    if expires_at < Utc::now().timestamp() || !admin_users().iter().any(|user| user == username) {
+        return None;
+    }
+
+    Some(username.to_string())
+}
+
+/// Returns the signed-in admin for the current request, or 401.
+pub async fn require_admin() -> Result<String, HttpError> {
+    let headers = FullstackContext::extract::<HeaderMap, _>()
+        .await
+        .or_else(|_| HttpError::unauthorized("not signed in"))?;
+
+    match session_user(&headers) {
+        Some(username) => Ok(username),
+        None => HttpError::unauthorized("not signed in"),
+    }
+}
@@ -0,0 +1,147 @@
+use crate::ssr::types::{Comment, CommentInput};
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+static COMMENT_RATE_LIMIT: crate::ssr::server_utils::RateLimit = crate::ssr::server_utils::RateLimit::new(
+    5,
+    std::time::Duration::from_secs(10 * 60),
+    "too many comments, please try again later",
+);
+
+#[cfg(feature = "server")]
+fn validate_comment_input(input: &CommentInput) -> Result<(), HttpError> {
+    use crate::ssr::server_utils::validate_email;
+
+    let name = input.author_name.trim();
+    if name.is_empty() || name.chars().count() > 100 {
+        return HttpError::bad_request("name is required (up to 100 characters)");
+    }
+    let email = input.email.trim();
+    if !email.is_empty() {
+        validate_email(email)?;
+    }
+    let body = input.body.trim().chars().count();
+    if !(2..=5000).contains(&body) {
+        return HttpError::bad_request("comment must be between 2 and 5000 characters");
+    }
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+pub(crate) fn format_comment_date(created_at: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(created_at)
+        .map(|date| date.format("%b %-d, %Y").to_string())
+        .unwrap_or_else(|_| created_at.to_string())
+}
+
+/// Approved comments on a live post, oldest first.
+#[get("/api/post/{slug}/comments")]
+pub async fn select_comments(slug: String) -> Result<Vec<Comment>> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::server_utils::validate_slug;
+
+        validate_slug(&slug)?;
+
+        let db = db().await;
+        let mut query = db
+            .query(
+                "
+        SELECT id, parent, author_name, body_html, <string>created_at AS created_at, created_at AS sort FROM comment
+            WHERE post.slug = $slug AND fn::post::is_live(post.is_published, post.publish_at) AND status = 'approved'
+            ORDER BY sort;
+        ",
+            )
+            .bind(("slug", slug))
+            .await?;
+        let mut comments = query.take::<Vec<Comment>>(0)?;
+        for comment in comments.iter_mut() {
+            comment.created_at = format_comment_date(&comment.created_at);
+        }
+
+        Ok(comments)
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+/// Queues a comment for moderation. Replies to a reply are attached to the
+/// top-level comment, so threads stay one level deep.
+#[post("/api/post/{slug}/comments")]
+pub async fn submit_comment(slug: String, input: CommentInput) -> Result<()> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::server_utils::{process_comment_markdown, record_id, request_ip, validate_slug};
+        use surrealdb_types::{RecordId, SurrealValue};
+
+        #[derive(SurrealValue)]
+        struct Parent {
+            id: RecordId,
+            parent: Option<RecordId>,
+        }
+
+        // Bots get the same answer as people, so they don't learn to skip it.
+        if !input.website.is_empty() {
+            return Ok(());
+        }
+
+        validate_slug(&slug)?;
+        validate_comment_input(&input)?;
+        let ip = request_ip().await;
+        COMMENT_RATE_LIMIT.check(ip.as_deref().unwrap_or("unknown")).await?;
+
+        let db = db().await;
+        let mut query = db
+            .query("SELECT VALUE id FROM post WHERE slug = $slug AND fn::post::is_live(is_published, publish_at);")
+            .bind(("slug", slug))
+            .await?;
+        let post = query
+            .take::<Vec<RecordId>>(0)?
+            .into_iter()
+            .next()
+            .or_not_found("post not found")?;
+
+        let parent = match input.parent.as_deref() {
+            Some(key) => {
+                let mut query = db
+                    .query("SELECT id, parent FROM comment WHERE id = $id AND post = $post AND status = 'approved';")
+                    .bind(("id", record_id("comment", key)?))
+                    .bind(("post", post.clone()))
+                    .await?;
+                let parent = query
+                    .take::<Vec<Parent>>(0)?
+                    .into_iter()
+                    .next()
+                    .or_not_found("comment not found")?;
+                Some(parent.parent.unwrap_or(parent.id))
+            }
+            None => None,
+        };
+
+        let email = Some(input.email.trim().to_lowercase()).filter(|email| !email.is_empty());
+        let body = input.body.trim().to_string();
+
+        db.query(
+            "CREATE comment SET post = $post, parent = $parent, author_name = $author_name, email = $email, body = $body, body_html = $body_html, ip = $ip;",
+        )
+        .bind(("post", post))
+        .bind(("parent", parent))
+        .bind(("author_name", input.author_name.trim().to_string()))
+        .bind(("email", email))
+        .bind(("body_html", process_comment_markdown(&body)))
+        .bind(("body", body))
+        .bind(("ip", ip))
+        .await?
+        .check()?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
@@ -0,0 +1,96 @@
+use crate::ssr::types::ContactInput;
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+static CONTACT_RATE_LIMIT: crate::ssr::server_utils::RateLimit = crate::ssr::server_utils::RateLimit::new(
+    3,
+    std::time::Duration::from_secs(10 * 60),
+    "too many messages, please try again later",
+);
+
+#[cfg(feature = "server")]
+fn validate_contact_input(input: &ContactInput) -> Result<(), HttpError> {
+    use crate::ssr::server_utils::validate_email;
+
+    let name = input.name.trim();
+    if name.is_empty() || name.chars().count() > 100 {
+        return HttpError::bad_request("name is required (up to 100 characters)");
+    }
+    validate_email(input.email.trim())?;
+    let message = input.message.trim().chars().count();
+    if !(10..=5000).contains(&message) {
+        return HttpError::bad_request("message must be between 10 and 5000 characters");
+    }
+    Ok(())
+}
+
+/// Stores the inquiry and forwards it to `CONTACT_TO` with the visitor as
+/// Reply-To. A failed forward is logged, not reported: the inquiry is saved.
+#[post("/api/contact")]
+pub async fn submit_contact(input: ContactInput) -> Result<()> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::mailer::{mailer, send, Email};
+        use crate::ssr::server_utils::{escape_html, request_ip};
+        use surrealdb_types::RecordId;
+
+        // Bots get the same answer as people, so they don't learn to skip it.
+        if !input.website.is_empty() {
+            return Ok(());
+        }
+
+        validate_contact_input(&input)?;
+        let ip = request_ip().await;
+        CONTACT_RATE_LIMIT.check(ip.as_deref().unwrap_or("unknown")).await?;
+
+        let name = input.name.trim().to_string();
+        let email = input.email.trim().to_string();
+        let message = input.message.trim().to_string();
+
+        let db = db().await;
+        let mut query = db
+            .query("CREATE contact_request SET name = $name, email = $email, message = $message, ip = $ip RETURN VALUE id;")
+            .bind(("name", name.clone()))
+            .bind(("email", email.clone()))
+            .bind(("message", message.clone()))
+            .bind(("ip", ip))
+            .await?;
+        let id = query
+            .take::<Option<RecordId>>(0)?
+            .or_internal_server_error("inquiry was not saved")?;
+
+        let forward = Email {
+            to: std::env::var("CONTACT_TO").unwrap_or("info@rust-dd.com".to_string()),
+            reply_to: Some(email.clone()),
+            subject: format!("Contact form: {name}"),
+            text: format!("From: {name} <{email}>\n\n{message}"),
+            html: format!(
+                "<p>From: {} &lt;{}&gt;</p><p style=\"white-space: pre-wrap\">{}</p>",
+                escape_html(&name),
+                escape_html(&email),
+                escape_html(&message)
+            ),
+            unsubscribe_url: None,
+        };
+        let sent = match mailer() {
+            Ok(mailer) => send(&mailer, forward).await,
+            Err(err) => Err(err),
+        };
+        match sent {
+            Ok(()) => {
+                db.query("UPDATE $id SET forwarded = true;")
+                    .bind(("id", id))
+                    .await?
+                    .check()?;
+            }
+            Err(err) => tracing::error!("failed to forward contact request: {err}"),
+        }
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
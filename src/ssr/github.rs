@@ -0,0 +1,602 @@
+use chrono::Utc;
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, ETAG, IF_NONE_MATCH, USER_AGENT};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use surrealdb_types::{Array, Datetime, RecordId, SurrealValue};
+use tokio::task::JoinSet;
+
+use crate::ssr::app_state::db;
+use crate::ssr::types::{DailyCount, RepoStats};
+
+/// How often `run` syncs. After a restart it waits out the rest of the
+/// interval since the last stored sync instead of refetching right away.
+const SYNC_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+
+/// Days of star history served with the stats.
+const HISTORY_DAYS: i64 = 90;
+
+/// Repos fetched at once during a sync. Each makes two requests in turn.
+const SYNC_CONCURRENCY: usize = 4;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Last good stats for a repo with the ETags they were served with, so the
+/// next sync can ask GitHub for changes only.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedRepo {
+    pub stats: RepoStats,
+    /// `YYYY-MM-DD` (UTC) of the sync that last reached GitHub.
+    pub day: String,
+    repo_etag: Option<String>,
+    release_etag: Option<String>,
+}
+
+/// A `repo_snapshot` row.
+#[derive(SurrealValue)]
+struct Snapshot {
+    github_repo: String,
+    day: String,
+    stars: u32,
+    forks: u32,
+    open_issues: u32,
+    latest_release: Option<String>,
+    pushed_at: Option<String>,
+    license: Option<String>,
+    repo_etag: Option<String>,
+    release_etag: Option<String>,
+}
+
+impl From<Snapshot> for CachedRepo {
+    fn from(snapshot: Snapshot) -> Self {
+        Self {
+            stats: RepoStats {
+                stars: snapshot.stars,
+                forks: snapshot.forks,
+                open_issues: snapshot.open_issues,
+                latest_release: snapshot.latest_release,
+                pushed_at: snapshot.pushed_at,
+                license: snapshot.license,
+                ..RepoStats::default()
+            },
+            day: snapshot.day,
+            repo_etag: snapshot.repo_etag,
+            release_etag: snapshot.release_etag,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GithubRepo {
+    stargazers_count: u32,
+    forks_count: u32,
+    open_issues_count: u32,
+    pushed_at: Option<String>,
+    license: Option<GithubLicense>,
+}
+
+#[derive(Deserialize)]
+struct GithubLicense {
+    spdx_id: Option<String>,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+}
+
+enum Fetched<T> {
+    Modified { body: T, etag: Option<String> },
+    NotModified,
+    NotFound,
+}
+
+pub struct GithubClient {
+    http: reqwest::Client,
+    api_url: String,
+}
+
+impl GithubClient {
+    pub fn new(api_url: &str, token: Option<&str>) -> Result<Self, String> {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/vnd.github+json"));
+        headers.insert(USER_AGENT, HeaderValue::from_static("rust-dd-blog"));
+        headers.insert("X-GitHub-Api-Version", HeaderValue::from_static("2022-11-28"));
+        if let Some(token) = token {
+            let value = HeaderValue::from_str(&format!("Bearer {token}")).map_err(|err| err.to_string())?;
+            headers.insert(AUTHORIZATION, value);
+        }
+
+        let http = reqwest::Client::builder()
+            .default_headers(headers)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|err| err.to_string())?;
+
+        Ok(Self {
+            http,
+            api_url: api_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// `GITHUB_API_URL` (default `https://api.github.com`) and the optional
+    /// `GITHUB_TOKEN`.
+    pub fn from_env() -> Result<Self, String> {
+        let api_url = env::var("GITHUB_API_URL").unwrap_or_else(|_| "https://api.github.com".to_string());
+        Self::new(&api_url, env::var("GITHUB_TOKEN").ok().as_deref())
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str, etag: Option<&str>) -> Result<Fetched<T>, String> {
+        let mut request = self.http.get(format!("{}{path}", self.api_url));
+        if let Some(etag) = etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+
+        let response = request.send().await.map_err(|err| err.to_string())?;
+        match response.status() {
+            StatusCode::NOT_MODIFIED => Ok(Fetched::NotModified),
+            StatusCode::NOT_FOUND => Ok(Fetched::NotFound),
+            status if status.is_success() => {
+                let etag = response
+                    .headers()
+                    .get(ETAG)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                let body = response.json::<T>().await.map_err(|err| err.to_string())?;
+                Ok(Fetched::Modified { body, etag })
+            }
+            status => Err(format!("GET {path}: {status}")),
+        }
+    }
+
+    /// Stats for `owner/repo`. Unchanged resources (304) keep the values in
+    /// `previous`; a repo without releases has no `latest_release`.
+    pub async fn fetch_repo(&self, repo: &str, previous: Option<&CachedRepo>) -> Result<CachedRepo, String> {
+        let mut cached = previous.cloned().unwrap_or_else(|| CachedRepo {
+            stats: RepoStats::default(),
+            day: String::new(),
+            repo_etag: None,
+            release_etag: None,
+        });
+
+        match self
+            .get::<GithubRepo>(&format!("/repos/{repo}"), cached.repo_etag.as_deref())
+            .await?
+        {
+            Fetched::Modified { body, etag } => {
+                cached.stats.stars = body.stargazers_count;
+                cached.stats.forks = body.forks_count;
+                cached.stats.open_issues = body.open_issues_count;
+                cached.stats.pushed_at = body.pushed_at;
+                cached.stats.license = body.license.map(|license| match license.spdx_id {
+                    Some(spdx_id) if spdx_id != "NOASSERTION" => spdx_id,
+                    _ => license.name,
+                });
+                cached.repo_etag = etag;
+            }
+            Fetched::NotModified => {}
+            Fetched::NotFound => return Err(format!("{repo} not found")),
+        }
+
+        match self
+            .get::<GithubRelease>(
+                &format!("/repos/{repo}/releases/latest"),
+                cached.release_etag.as_deref(),
+            )
+            .await?
+        {
+            Fetched::Modified { body, etag } => {
+                cached.stats.latest_release = Some(body.tag_name);
+                cached.release_etag = etag;
+            }
+            Fetched::NotModified => {}
+            Fetched::NotFound => {
+                cached.stats.latest_release = None;
+                cached.release_etag = None;
+            }
+        }
+
+        cached.day = today();
+        Ok(cached)
+    }
+}
+
+/// Fetches `repos` with at most `concurrency` repos in flight. A repo that
+/// fails keeps its entry from `previous`, so a GitHub outage or rate limit
+/// never replaces good numbers with nothing.
+pub async fn sync(
+    client: Arc<GithubClient>,
+    repos: Vec<String>,
+    previous: &BTreeMap<String, CachedRepo>,
+    concurrency: usize,
+) -> BTreeMap<String, CachedRepo> {
+    let mut pending = repos.into_iter();
+    let mut tasks = JoinSet::new();
+    let mut synced = BTreeMap::new();
+
+    loop {
+        while tasks.len() < concurrency.max(1) {
+            let Some(repo) = pending.next() else {
+                break;
+            };
+            let client = client.clone();
+            let cached = previous.get(&repo).cloned();
+            tasks.spawn(async move {
+                let result = client.fetch_repo(&repo, cached.as_ref()).await;
+                (repo, result)
+            });
+        }
+
+        let Some(joined) = tasks.join_next().await else {
+            break;
+        };
+        let Ok((repo, result)) = joined else {
+            continue;
+        };
+        match result {
+            Ok(cached) => {
+                synced.insert(repo, cached);
+            }
+            Err(err) => {
+                tracing::warn!("github sync failed for {repo}: {err}");
+                if let Some(cached) = previous.get(&repo) {
+                    synced.insert(repo, cached.clone());
+                }
+            }
+        }
+    }
+
+    synced
+}
+
+fn today() -> String {
+    Utc::now().date_naive().to_string()
+}
+
+fn days_ago(days: i64) -> String {
+    (Utc::now().date_naive() - chrono::Duration::days(days)).to_string()
+}
+
+/// Snapshots from `since` on, oldest first.
+async fn load_snapshots(since: String) -> surrealdb::Result<Vec<Snapshot>> {
+    let db = db().await;
+    let mut query = db
+        .query(
+            "SELECT github_repo, day, stars, forks, open_issues, latest_release, pushed_at, license, repo_etag, release_etag
+                FROM repo_snapshot WHERE day >= $since ORDER BY day;",
+        )
+        .bind(("since", since))
+        .await?;
+
+    query.take::<Vec<Snapshot>>(0)
+}
+
+async fn last_synced_at() -> surrealdb::Result<Option<Datetime>> {
+    let db = db().await;
+    let mut query = db
+        .query("SELECT VALUE synced_at FROM repo_snapshot ORDER BY synced_at DESC LIMIT 1;")
+        .await?;
+
+    Ok(query.take::<Vec<Datetime>>(0)?.into_iter().next())
+}
+
+/// Syncs every visible `oss_project` and stores today's snapshot for each
+/// repo GitHub answered for. The ETags come from the latest stored snapshot.
+async fn sync_once() -> Result<(), String> {
+    let db = db().await;
+    let repos = db
+        .query("SELECT VALUE github_repo FROM oss_project WHERE is_visible = true;")
+        .await
+        .and_then(|mut query| query.take::<Vec<String>>(0))
+        .map_err(|err| err.to_string())?;
+
+    let previous = load_snapshots(days_ago(HISTORY_DAYS))
+        .await
+        .map_err(|err| err.to_string())?
+        .into_iter()
+        .map(|snapshot| (snapshot.github_repo.clone(), CachedRepo::from(snapshot)))
+        .collect::<BTreeMap<_, _>>();
+
+    let client = Arc::new(GithubClient::from_env()?);
+    let today = today();
+    for (repo, cached) in sync(client, repos, &previous, SYNC_CONCURRENCY).await {
+        if cached.day != today {
+            continue;
+        }
+        let snapshot = Snapshot {
+            github_repo: repo.clone(),
+            day: cached.day,
+            stars: cached.stats.stars,
+            forks: cached.stats.forks,
+            open_issues: cached.stats.open_issues,
+            latest_release: cached.stats.latest_release,
+            pushed_at: cached.stats.pushed_at,
+            license: cached.stats.license,
+            repo_etag: cached.repo_etag,
+            release_etag: cached.release_etag,
+        };
+        db.query("UPSERT $id CONTENT $snapshot;")
+            .bind((
+                "id",
+                RecordId::new("repo_snapshot", Array::from(vec![repo, today.clone()])),
+            ))
+            .bind(("snapshot", snapshot))
+            .await
+            .and_then(|response| response.check())
+            .map_err(|err| err.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Keeps `repo_snapshot` up to date, syncing once every `SYNC_INTERVAL`.
+/// Only the first sync is timed from the stored snapshots; after that each
+/// attempt is followed by a full interval, or `RETRY_DELAY` when it failed,
+/// so a sync that stores nothing never turns into a busy loop.
+pub async fn run() {
+    let first_sync_in = match last_synced_at().await {
+        Ok(Some(synced_at)) => (SYNC_INTERVAL.as_secs() as i64 - (Utc::now() - *synced_at).num_seconds()).max(0),
+        Ok(None) => 0,
+        Err(err) => {
+            tracing::error!("github sync failed to load the last sync: {err}");
+            0
+        }
+    };
+    tokio::time::sleep(Duration::from_secs(first_sync_in as u64)).await;
+
+    loop {
+        let sleep = match sync_once().await {
+            Ok(()) => SYNC_INTERVAL,
+            Err(err) => {
+                tracing::error!("github sync failed: {err}");
+                RETRY_DELAY
+            }
+        };
+        tokio::time::sleep(sleep).await;
+    }
+}
+
+/// Stars gained since `month_start`, measured from the last snapshot before
+/// it, or the first one after it when the history starts this month.
+fn gained_since(history: &[Snapshot], month_start: &str) -> i64 {
+    let (Some(first), Some(latest)) = (history.first(), history.last()) else {
+        return 0;
+    };
+    let baseline = history
+        .iter()
+        .rev()
+        .find(|snapshot| snapshot.day.as_str() < month_start)
+        .unwrap_or(first);
+
+    i64::from(latest.stars) - i64::from(baseline.stars)
+}
+
+/// Latest stored stats for each repo, with its star history over the last
+/// `HISTORY_DAYS`. Repos never synced are missing from the map.
+pub async fn repo_stats() -> surrealdb::Result<BTreeMap<String, RepoStats>> {
+    let month_start = Utc::now().date_naive().format("%Y-%m-01").to_string();
+    let mut histories = BTreeMap::<String, Vec<Snapshot>>::new();
+    for snapshot in load_snapshots(days_ago(HISTORY_DAYS)).await? {
+        histories
+            .entry(snapshot.github_repo.clone())
+            .or_default()
+            .push(snapshot);
+    }
+
+    Ok(histories
+        .into_iter()
+        .filter_map(|(repo, history)| {
+            let gained_this_month = gained_since(&history, &month_start);
+            let star_history = history
+                .iter()
+                .map(|snapshot| DailyCount {
+                    day: snapshot.day.clone(),
+                    count: snapshot.stars as usize,
+                })
+                .collect();
+            let latest = history.into_iter().next_back()?;
+            let stats = RepoStats {
+                star_history,
+                gained_this_month,
+                ..CachedRepo::from(latest).stats
+            };
+            Some((repo, stats))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::{Path, State};
+    use axum::http::{header, HeaderMap as AxumHeaders, StatusCode as AxumStatus};
+    use axum::response::{IntoResponse, Response};
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use serde_json::json;
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A stand-in for api.github.com serving `rust-dd/<name>` repos. Repos in
+    /// `failing` answer 500; `no-release` has no releases.
+    #[derive(Default)]
+    struct MockGithub {
+        failing: std::sync::Mutex<HashSet<String>>,
+        requests: AtomicUsize,
+        not_modified: AtomicUsize,
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+        delay: Duration,
+    }
+
+    impl MockGithub {
+        async fn enter(&self, name: &str, headers: &AxumHeaders, etag: &str) -> Option<Response> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            if self.failing.lock().unwrap().contains(name) {
+                return Some(AxumStatus::INTERNAL_SERVER_ERROR.into_response());
+            }
+            if headers.get(header::IF_NONE_MATCH).and_then(|value| value.to_str().ok()) == Some(etag) {
+                self.not_modified.fetch_add(1, Ordering::SeqCst);
+                return Some(AxumStatus::NOT_MODIFIED.into_response());
+            }
+            None
+        }
+    }
+
+    async fn repo(State(mock): State<Arc<MockGithub>>, Path(name): Path<String>, headers: AxumHeaders) -> Response {
+        let etag = format!("\"repo-{name}\"");
+        if let Some(response) = mock.enter(&name, &headers, &etag).await {
+            return response;
+        }
+        let body = json!({
+            "stargazers_count": name.len() * 10,
+            "forks_count": 3,
+            "open_issues_count": 5,
+            "pushed_at": "2025-01-02T03:04:05Z",
+            "license": { "spdx_id": "MIT", "name": "MIT License" },
+        });
+        ([(header::ETAG, etag)], Json(body)).into_response()
+    }
+
+    async fn latest_release(
+        State(mock): State<Arc<MockGithub>>,
+        Path(name): Path<String>,
+        headers: AxumHeaders,
+    ) -> Response {
+        let etag = format!("\"release-{name}\"");
+        if let Some(response) = mock.enter(&name, &headers, &etag).await {
+            return response;
+        }
+        if name == "no-release" {
+            return AxumStatus::NOT_FOUND.into_response();
+        }
+        ([(header::ETAG, etag)], Json(json!({ "tag_name": "v1.2.0" }))).into_response()
+    }
+
+    async fn serve(mock: Arc<MockGithub>) -> Arc<GithubClient> {
+        let app = Router::new()
+            .route("/repos/rust-dd/{name}", get(repo))
+            .route("/repos/rust-dd/{name}/releases/latest", get(latest_release))
+            .with_state(mock);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        Arc::new(GithubClient::new(&url, None).unwrap())
+    }
+
+    fn repos(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| format!("rust-dd/{name}")).collect()
+    }
+
+    #[tokio::test]
+    async fn collects_repo_and_release_stats() {
+        let mock = Arc::new(MockGithub::default());
+        let client = serve(mock.clone()).await;
+
+        let synced = sync(client, repos(&["tako", "no-release"]), &BTreeMap::new(), 4).await;
+
+        assert_eq!(
+            synced["rust-dd/tako"].stats,
+            RepoStats {
+                stars: 40,
+                forks: 3,
+                open_issues: 5,
+                latest_release: Some("v1.2.0".to_string()),
+                pushed_at: Some("2025-01-02T03:04:05Z".to_string()),
+                license: Some("MIT".to_string()),
+                ..RepoStats::default()
+            }
+        );
+        assert_eq!(synced["rust-dd/no-release"].stats.latest_release, None);
+        assert_eq!(synced["rust-dd/no-release"].stats.stars, 100);
+    }
+
+    #[tokio::test]
+    async fn resync_sends_etags_and_keeps_values_on_304() {
+        let mock = Arc::new(MockGithub::default());
+        let client = serve(mock.clone()).await;
+
+        let first = sync(client.clone(), repos(&["tako", "ito"]), &BTreeMap::new(), 4).await;
+        assert_eq!(mock.not_modified.load(Ordering::SeqCst), 0);
+
+        let second = sync(client, repos(&["tako", "ito"]), &first, 4).await;
+        assert_eq!(mock.not_modified.load(Ordering::SeqCst), 4);
+        assert_eq!(second, first);
+    }
+
+    #[tokio::test]
+    async fn failed_repos_keep_their_last_good_values() {
+        let mock = Arc::new(MockGithub::default());
+        let client = serve(mock.clone()).await;
+
+        let first = sync(client.clone(), repos(&["tako", "ito"]), &BTreeMap::new(), 4).await;
+        mock.failing
+            .lock()
+            .unwrap()
+            .extend(["tako".to_string(), "new-repo".to_string()]);
+
+        let second = sync(client, repos(&["tako", "ito", "new-repo"]), &first, 4).await;
+        assert_eq!(second["rust-dd/tako"], first["rust-dd/tako"]);
+        assert_eq!(second["rust-dd/ito"].stats, first["rust-dd/ito"].stats);
+        assert!(!second.contains_key("rust-dd/new-repo"));
+    }
+
+    #[tokio::test]
+    async fn never_exceeds_the_concurrency_limit() {
+        let mock = Arc::new(MockGithub {
+            delay: Duration::from_millis(20),
+            ..MockGithub::default()
+        });
+        let client = serve(mock.clone()).await;
+        let names = (0..12).map(|index| format!("repo-{index}")).collect::<Vec<_>>();
+        let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+
+        let synced = sync(client, repos(&names), &BTreeMap::new(), 3).await;
+
+        assert_eq!(synced.len(), 12);
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 24);
+        let max_in_flight = mock.max_in_flight.load(Ordering::SeqCst);
+        assert!((2..=3).contains(&max_in_flight), "{max_in_flight} requests in flight");
+    }
+
+    fn snapshot(day: &str, stars: u32) -> Snapshot {
+        Snapshot {
+            github_repo: "rust-dd/tako".to_string(),
+            day: day.to_string(),
+            stars,
+            forks: 0,
+            open_issues: 0,
+            latest_release: None,
+            pushed_at: None,
+            license: None,
+            repo_etag: None,
+            release_etag: None,
+        }
+    }
+
+    #[test]
+    fn gained_this_month_counts_from_the_end_of_last_month() {
+        let history = [
+            snapshot("2025-02-20", 10),
+            snapshot("2025-02-28", 12),
+            snapshot("2025-03-02", 15),
+            snapshot("2025-03-09", 19),
+        ];
+        assert_eq!(gained_since(&history, "2025-03-01"), 7);
+        assert_eq!(gained_since(&history[2..], "2025-03-01"), 4);
+        assert_eq!(gained_since(&history[..1], "2025-03-01"), 0);
+        assert_eq!(gained_since(&[], "2025-03-01"), 0);
+    }
+}
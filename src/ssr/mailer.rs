@@ -0,0 +1,103 @@
+use std::env;
+
+use axum::http::StatusCode;
+use dioxus::prelude::HttpError;
+use lettre::message::header::{HeaderName, HeaderValue};
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::transport::smtp::client::Tls;
+use lettre::Address;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+pub type Mailer = AsyncSmtpTransport<Tokio1Executor>;
+
+/// A plain-text + HTML email. `unsubscribe_url` adds the RFC 8058 one-click
+/// unsubscribe headers that mail clients surface as an "Unsubscribe" button.
+pub struct Email {
+    pub to: String,
+    pub reply_to: Option<String>,
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+    pub unsubscribe_url: Option<String>,
+}
+
+fn not_configured() -> HttpError {
+    HttpError::new(StatusCode::SERVICE_UNAVAILABLE, "email is not configured")
+}
+
+/// Builds the SMTP transport from `SMTP_HOST`, `SMTP_PORT`, `SMTP_USERNAME`,
+/// `SMTP_PASSWORD` and `SMTP_TLS` (`starttls`, `tls` or `none`). `none` is
+/// for a local SMTP stand-in such as Mailpit during development.
+pub fn mailer() -> Result<Mailer, HttpError> {
+    let host = env::var("SMTP_HOST").map_err(|_| not_configured())?;
+    let tls = env::var("SMTP_TLS").unwrap_or("starttls".to_string());
+
+    let mut builder = match tls.as_str() {
+        "tls" => Mailer::relay(&host),
+        "starttls" => Mailer::starttls_relay(&host),
+        "none" => Ok(Mailer::builder_dangerous(&host).tls(Tls::None)),
+        _ => return Err(not_configured()),
+    }
+    .map_err(|_| not_configured())?;
+
+    if let Some(port) = env::var("SMTP_PORT").ok().and_then(|port| port.parse::<u16>().ok()) {
+        builder = builder.port(port);
+    }
+    if let (Ok(username), Ok(password)) = (env::var("SMTP_USERNAME"), env::var("SMTP_PASSWORD")) {
+        builder = builder.credentials(Credentials::new(username, password));
+    }
+
+    Ok(builder.build())
+}
+
+/// `NEWSLETTER_FROM`, or the site's short name at `newsletter@` its domain.
+fn sender() -> Result<Mailbox, HttpError> {
+    if let Ok(from) = env::var("NEWSLETTER_FROM") {
+        return from.parse().map_err(|_| not_configured());
+    }
+
+    let site = crate::seo::site();
+    let domain = site.url.trim_start_matches("https://").trim_start_matches("http://");
+    let domain = domain.split(['/', ':']).next().unwrap_or_default();
+    let address = Address::new("newsletter", domain).map_err(|_| not_configured())?;
+    Ok(Mailbox::new(Some(site.short_name.clone()), address))
+}
+
+pub async fn send(mailer: &Mailer, email: Email) -> Result<(), HttpError> {
+    let to = email
+        .to
+        .parse::<Mailbox>()
+        .or_else(|_| HttpError::bad_request("invalid email address"))?;
+
+    let mut builder = Message::builder().from(sender()?).to(to).subject(email.subject);
+    if let Some(reply_to) = email.reply_to {
+        let reply_to = reply_to
+            .parse::<Mailbox>()
+            .or_else(|_| HttpError::bad_request("invalid email address"))?;
+        builder = builder.reply_to(reply_to);
+    }
+
+    let mut message = builder
+        .multipart(MultiPart::alternative_plain_html(email.text, email.html))
+        .or_else(|_| HttpError::internal_server_error("failed to build email"))?;
+
+    if let Some(url) = email.unsubscribe_url {
+        let headers = message.headers_mut();
+        headers.insert_raw(HeaderValue::new(
+            HeaderName::new_from_ascii_str("List-Unsubscribe"),
+            format!("<{url}>"),
+        ));
+        headers.insert_raw(HeaderValue::new(
+            HeaderName::new_from_ascii_str("List-Unsubscribe-Post"),
+            "List-Unsubscribe=One-Click".to_string(),
+        ));
+    }
+
+    mailer.send(message).await.map_err(|err| {
+        tracing::error!("failed to send email: {err}");
+        HttpError::new(StatusCode::BAD_GATEWAY, "failed to send email")
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,631 @@
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+use std::time::Duration;
+
+#[cfg(feature = "server")]
+use axum::http::StatusCode;
+
+#[cfg(feature = "server")]
+use surrealdb_types::{RecordId, SurrealValue};
+
+#[cfg(feature = "server")]
+const CONFIRM_TOKEN_TTL_SECS: i64 = 48 * 60 * 60;
+
+/// Repeated sign-ups for the same address only trigger a new confirmation
+/// email after this long, so the form can't be used to flood an inbox.
+#[cfg(feature = "server")]
+const CONFIRM_RESEND_AFTER: Duration = Duration::from_secs(10 * 60);
+
+#[cfg(feature = "server")]
+static SUBSCRIBE_RATE_LIMIT: crate::ssr::server_utils::RateLimit = crate::ssr::server_utils::RateLimit::new(
+    5,
+    Duration::from_secs(60 * 60),
+    "too many sign-ups, please try again later",
+);
+
+/// How far back `send_new_posts` looks for unsent posts, so the first run
+/// doesn't mail the whole archive.
+#[cfg(feature = "server")]
+const NEW_POST_WINDOW_DAYS: i64 = 14;
+
+#[cfg(feature = "server")]
+const CONFIRM: &str = "confirm";
+
+#[cfg(feature = "server")]
+const UNSUBSCRIBE: &str = "unsubscribe";
+
+#[cfg(feature = "server")]
+fn newsletter_secret() -> Result<Vec<u8>, HttpError> {
+    match std::env::var("NEWSLETTER_SECRET") {
+        Ok(secret) if secret.len() >= 32 => Ok(secret.into_bytes()),
+        _ => Err(HttpError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "newsletter is not configured",
+        )),
+    }
+}
+
+/// Tokens are `{subscriber key}.{action}.{expires_at}.{hmac}`, with
+/// `expires_at` 0 for tokens that never expire (unsubscribe links).
+#[cfg(feature = "server")]
+fn issue_token(key: &str, action: &str, expires_at: i64) -> Result<String, HttpError> {
+    use crate::ssr::auth::sign;
+
+    let payload = format!("{key}.{action}.{expires_at}");
+    Ok(format!("{payload}.{}", sign(&newsletter_secret()?, &payload)))
+}
+
+#[cfg(feature = "server")]
+fn verify_token(token: &str, action: &str) -> Result<surrealdb_types::RecordId, HttpError> {
+    use crate::ssr::auth::verify;
+    use crate::ssr::server_utils::record_id;
+
+    let secret = newsletter_secret()?;
+    let invalid = || HttpError::bad_request("invalid or expired link");
+
+    let Some((payload, signature)) = token.rsplit_once('.') else {
+        return invalid();
+    };
+    if !verify(&secret, payload, signature) {
+        return invalid();
+    }
+
+    let mut parts = payload.splitn(3, '.');
+    let (Some(key), Some(token_action), Some(expires_at)) = (parts.next(), parts.next(), parts.next()) else {
+        return invalid();
+    };
+    let Ok(expires_at) = expires_at.parse::<i64>() else {
+        return invalid();
+    };
+    if token_action != action || (expires_at != 0 && expires_at < chrono::Utc::now().timestamp()) {
+        return invalid();
+    }
+
+    record_id("subscriber", key)
+}
+
+/// The double opt-in email for subscriber `key`, with a confirmation link
+/// valid for `CONFIRM_TOKEN_TTL_SECS`.
+#[cfg(feature = "server")]
+fn confirmation_email(email: String, key: &str) -> Result<crate::ssr::mailer::Email, HttpError> {
+    use crate::ssr::mailer::Email;
+    use crate::ssr::server_utils::escape_html;
+
+    let expires_at = chrono::Utc::now().timestamp() + CONFIRM_TOKEN_TTL_SECS;
+    let site = crate::seo::site();
+    let confirm_url = site.absolute_url(&format!(
+        "/newsletter/confirm?token={}",
+        issue_token(key, CONFIRM, expires_at)?
+    ));
+
+    Ok(Email {
+        to: email,
+        subject: format!("Confirm your {} subscription", site.short_name),
+        text: format!(
+            "Confirm your subscription to the {} blog:\n\n{confirm_url}\n\nThe link is valid for 48 hours. If you didn't sign up, ignore this email.",
+            site.short_name
+        ),
+        html: format!(
+            r#"<p>Confirm your subscription to the {} blog:</p><p><a href="{confirm_url}">Confirm subscription</a></p><p>The link is valid for 48 hours. If you didn't sign up, ignore this email.</p>"#,
+            escape_html(&site.short_name)
+        ),
+        reply_to: None,
+        unsubscribe_url: None,
+    })
+}
+
+/// Starts a double opt-in: records the address as pending and emails a
+/// confirmation link. Always succeeds for already-confirmed addresses, so
+/// the form doesn't reveal who is subscribed.
+#[post("/api/newsletter/subscribe")]
+pub async fn subscribe(email: String) -> Result<()> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+        use crate::ssr::mailer::{mailer, send};
+        use crate::ssr::server_utils::{request_ip, validate_email};
+        use surrealdb_types::{Datetime, RecordId, SurrealValue, ToSql};
+
+        #[derive(SurrealValue)]
+        struct Subscriber {
+            id: RecordId,
+            status: String,
+            confirm_sent_at: Option<Datetime>,
+        }
+
+        let email = email.trim().to_lowercase();
+        validate_email(&email)?;
+        let ip = request_ip().await;
+        SUBSCRIBE_RATE_LIMIT.check(ip.as_deref().unwrap_or("unknown")).await?;
+
+        let db = db().await;
+        let mut query = db
+            .query("SELECT id, status, confirm_sent_at FROM subscriber WHERE email = $email;")
+            .bind(("email", email.clone()))
+            .await?;
+        let existing = query.take::<Vec<Subscriber>>(0)?.into_iter().next();
+
+        let id = match existing {
+            Some(subscriber) if subscriber.status == "confirmed" => return Ok(()),
+            Some(subscriber) => {
+                let recently_sent = subscriber.confirm_sent_at.is_some_and(|sent_at| {
+                    (chrono::Utc::now() - *sent_at).to_std().unwrap_or_default() < CONFIRM_RESEND_AFTER
+                });
+                if recently_sent {
+                    return Ok(());
+                }
+                db.query("UPDATE $id SET status = 'pending';")
+                    .bind(("id", subscriber.id.clone()))
+                    .await?
+                    .check()?;
+                subscriber.id
+            }
+            None => {
+                let mut query = db
+                    .query("CREATE subscriber SET email = $email RETURN VALUE id;")
+                    .bind(("email", email.clone()))
+                    .await?;
+                query
+                    .take::<Option<RecordId>>(0)?
+                    .or_internal_server_error("subscriber was not created")?
+            }
+        };
+
+        // Stamped only once the mail is out, so a failed send can be retried
+        // right away instead of waiting out `CONFIRM_RESEND_AFTER`.
+        send(&mailer()?, confirmation_email(email, &id.key.to_sql())?).await?;
+        db.query("UPDATE $id SET confirm_sent_at = time::now();")
+            .bind(("id", id))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[post("/api/newsletter/confirm")]
+pub async fn confirm_subscription(token: String) -> Result<()> {
+    #[cfg(feature = "server")]
+    {
+        use crate::ssr::app_state::db;
+
+        let id = verify_token(&token, CONFIRM)?;
+
+        let db = db().await;
+        db.query("UPDATE $id SET status = 'confirmed', confirmed_at = time::now() WHERE status = 'pending';")
+            .bind(("id", id))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[post("/api/newsletter/unsubscribe")]
+pub async fn unsubscribe(token: String) -> Result<()> {
+    #[cfg(feature = "server")]
+    {
+        unsubscribe_token(&token).await?;
+
+        Ok(())
+    }
+    #[cfg(not(feature = "server"))]
+    {
+        unreachable!()
+    }
+}
+
+#[cfg(feature = "server")]
+async fn unsubscribe_token(token: &str) -> Result<()> {
+    use crate::ssr::app_state::db;
+
+    let id = verify_token(token, UNSUBSCRIBE)?;
+
+    let db = db().await;
+    db.query("UPDATE $id SET status = 'unsubscribed', unsubscribed_at = time::now() WHERE status != 'unsubscribed';")
+        .bind(("id", id))
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+#[derive(serde::Deserialize)]
+pub struct OneClickQuery {
+    token: String,
+}
+
+/// RFC 8058 one-click unsubscribe: mail clients POST here from the
+/// `List-Unsubscribe` header without opening the page.
+#[cfg(feature = "server")]
+pub async fn one_click_unsubscribe_handler(
+    axum::extract::Query(query): axum::extract::Query<OneClickQuery>,
+) -> StatusCode {
+    match unsubscribe_token(&query.token).await {
+        Ok(()) => StatusCode::OK,
+        Err(_) => StatusCode::BAD_REQUEST,
+    }
+}
+
+/// `NEWSLETTER_SEND_ON_PUBLISH=true` sends the newsletter as soon as a
+/// scheduled post goes live instead of waiting for `send-newsletter`.
+#[cfg(feature = "server")]
+pub fn send_on_publish_enabled() -> bool {
+    std::env::var("NEWSLETTER_SEND_ON_PUBLISH").is_ok_and(|value| value == "true")
+}
+
+/// Runs `send_new_posts` each time the publisher announces a post. Posts
+/// that go live together are sent by the first run; the rest find nothing
+/// left to send.
+#[cfg(feature = "server")]
+pub async fn send_on_publish(mut published: tokio::sync::broadcast::Receiver<crate::ssr::publisher::PublishedPost>) {
+    use tokio::sync::broadcast::error::RecvError;
+
+    loop {
+        match published.recv().await {
+            Ok(_) | Err(RecvError::Lagged(_)) => {}
+            Err(RecvError::Closed) => return,
+        }
+        match send_new_posts().await {
+            Ok((0, _)) => {}
+            Ok((posts, sent)) => tracing::info!("sent {posts} new posts to {sent} subscribers"),
+            Err(err) => tracing::error!("failed to send newsletter for new posts: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+#[derive(SurrealValue)]
+struct NewPost {
+    id: RecordId,
+    title: String,
+    summary: String,
+    slug: Option<String>,
+}
+
+#[cfg(feature = "server")]
+#[derive(SurrealValue)]
+struct Recipient {
+    id: RecordId,
+    email: String,
+}
+
+/// One digest of `posts` for `recipient`, with their unsubscribe links.
+#[cfg(feature = "server")]
+fn digest(posts: &[&NewPost], recipient: &Recipient) -> Result<crate::ssr::mailer::Email, HttpError> {
+    use crate::ssr::mailer::Email;
+    use crate::ssr::server_utils::escape_html;
+    use surrealdb_types::ToSql;
+
+    let site = crate::seo::site();
+    let subject = match posts {
+        [post] => post.title.clone(),
+        posts => format!("{} new posts on {}", posts.len(), site.short_name),
+    };
+    let links = posts
+        .iter()
+        .map(|post| {
+            let url = site.absolute_url(&format!("/post/{}", post.slug.clone().unwrap_or_default()));
+            (post, url)
+        })
+        .collect::<Vec<_>>();
+    let text = links
+        .iter()
+        .map(|(post, url)| format!("{}\n{}\n{url}", post.title, post.summary))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let html = links
+        .iter()
+        .map(|(post, url)| {
+            format!(
+                r#"<h2><a href="{url}">{}</a></h2><p>{}</p>"#,
+                escape_html(&post.title),
+                escape_html(&post.summary)
+            )
+        })
+        .collect::<String>();
+
+    let token = issue_token(&recipient.id.key.to_sql(), UNSUBSCRIBE, 0)?;
+    let unsubscribe_url = site.absolute_url(&format!("/newsletter/unsubscribe?token={token}"));
+    Ok(Email {
+        to: recipient.email.clone(),
+        subject,
+        text: format!("{text}\n\n--\nUnsubscribe: {unsubscribe_url}"),
+        html: format!(r#"{html}<hr><p><a href="{unsubscribe_url}">Unsubscribe</a></p>"#),
+        reply_to: None,
+        unsubscribe_url: Some(site.absolute_url(&format!("/newsletter/unsubscribe/one-click?token={token}"))),
+    })
+}
+
+/// Emails every live post published in the last `NEW_POST_WINDOW_DAYS` that
+/// hasn't been sent yet to all confirmed subscribers, one digest each.
+/// Each delivery is recorded in `newsletter_delivery`, so a rerun only
+/// emails the subscribers a post hasn't reached. Posts are marked as sent
+/// once every subscriber has them; if no email goes out at all, nothing is
+/// recorded and an error is returned. Returns `(posts, emails sent)`.
+#[cfg(feature = "server")]
+pub async fn send_new_posts() -> Result<(usize, usize)> {
+    use crate::ssr::app_state::db;
+    use crate::ssr::mailer::{mailer, send};
+    use chrono::Utc;
+    use std::collections::HashSet;
+    use surrealdb_types::{Array, Datetime, ToSql};
+
+    #[derive(SurrealValue)]
+    struct Delivery {
+        post: RecordId,
+        subscriber: RecordId,
+    }
+
+    let db = db().await;
+    let mut query = db
+        .query(
+            "
+        SELECT id, title, summary, slug, created_at FROM post
+            WHERE fn::post::is_live(is_published, publish_at) AND newsletter_sent_at = NONE
+                AND (publish_at ?? created_at) > $since
+            ORDER BY created_at;
+        SELECT id, email FROM subscriber WHERE status = 'confirmed';
+        ",
+        )
+        .bind((
+            "since",
+            Datetime::from(Utc::now() - chrono::Duration::days(NEW_POST_WINDOW_DAYS)),
+        ))
+        .await?;
+    let posts = query.take::<Vec<NewPost>>(0)?;
+    let recipients = query.take::<Vec<Recipient>>(1)?;
+
+    if posts.is_empty() {
+        return Ok((0, 0));
+    }
+
+    let mut query = db
+        .query("SELECT post, subscriber FROM newsletter_delivery WHERE post IN $posts;")
+        .bind(("posts", posts.iter().map(|post| post.id.clone()).collect::<Vec<_>>()))
+        .await?;
+    let delivered = query
+        .take::<Vec<Delivery>>(0)?
+        .into_iter()
+        .map(|delivery| (delivery.post.to_sql(), delivery.subscriber.to_sql()))
+        .collect::<HashSet<_>>();
+
+    let mailer = mailer()?;
+    let (mut sent, mut failed) = (0, 0);
+    for recipient in recipients {
+        let pending = posts
+            .iter()
+            .filter(|post| !delivered.contains(&(post.id.to_sql(), recipient.id.to_sql())))
+            .collect::<Vec<_>>();
+        if pending.is_empty() {
+            continue;
+        }
+
+        if let Err(err) = send(&mailer, digest(&pending, &recipient)?).await {
+            tracing::error!("failed to email {}: {err}", recipient.id.to_sql());
+            failed += 1;
+            continue;
+        }
+        sent += 1;
+
+        for post in pending {
+            db.query("UPSERT $id SET post = $post, subscriber = $subscriber;")
+                .bind((
+                    "id",
+                    RecordId::new(
+                        "newsletter_delivery",
+                        Array::from(vec![post.id.clone(), recipient.id.clone()]),
+                    ),
+                ))
+                .bind(("post", post.id.clone()))
+                .bind(("subscriber", recipient.id.clone()))
+                .await?
+                .check()?;
+        }
+    }
+
+    if failed > 0 {
+        if sent == 0 {
+            return Err(HttpError::new(
+                StatusCode::BAD_GATEWAY,
+                format!("failed to email all {failed} subscribers"),
+            )
+            .into());
+        }
+        tracing::warn!("{failed} subscribers were not reached; rerun to retry them");
+        return Ok((posts.len(), sent));
+    }
+
+    db.query("UPDATE $ids SET newsletter_sent_at = time::now();")
+        .bind(("ids", posts.iter().map(|post| post.id.clone()).collect::<Vec<_>>()))
+        .await?
+        .check()?;
+
+    Ok((posts.len(), sent))
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    use crate::ssr::mailer::{send, Mailer};
+    use lettre::transport::smtp::client::Tls;
+    use std::sync::Once;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    fn configure() {
+        static CONFIGURE: Once = Once::new();
+        CONFIGURE.call_once(|| std::env::set_var("NEWSLETTER_SECRET", "test-secret-that-is-at-least-32-bytes"));
+    }
+
+    /// A local SMTP stand-in that accepts every message and passes its raw
+    /// contents on. Recipients in `reject` are refused with a 550.
+    async fn smtp_server(reject: &'static [&'static str]) -> (Mailer, mpsc::UnboundedReceiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (messages, received) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let messages = messages.clone();
+                tokio::spawn(async move {
+                    let (read, mut write) = stream.into_split();
+                    let mut lines = BufReader::new(read).lines();
+                    write.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        let command = line.to_ascii_uppercase();
+                        let reply: &[u8] = if command.starts_with("EHLO") || command.starts_with("HELO") {
+                            b"250 localhost\r\n"
+                        } else if command.starts_with("RCPT") && reject.iter().any(|address| line.contains(address)) {
+                            b"550 mailbox unavailable\r\n"
+                        } else if command.starts_with("DATA") {
+                            write.write_all(b"354 go ahead\r\n").await.unwrap();
+                            let mut data = String::new();
+                            while let Ok(Some(line)) = lines.next_line().await {
+                                if line == "." {
+                                    break;
+                                }
+                                data.push_str(&line);
+                                data.push('\n');
+                            }
+                            let _ = messages.send(data);
+                            b"250 queued\r\n"
+                        } else if command.starts_with("QUIT") {
+                            write.write_all(b"221 bye\r\n").await.unwrap();
+                            break;
+                        } else {
+                            b"250 ok\r\n"
+                        };
+                        write.write_all(reply).await.unwrap();
+                    }
+                });
+            }
+        });
+
+        let mailer = Mailer::builder_dangerous("127.0.0.1").port(port).tls(Tls::None).build();
+        (mailer, received)
+    }
+
+    /// Undoes the quoted-printable encoding lettre applies to the bodies.
+    fn decode_quoted_printable(message: &str) -> String {
+        let bytes = message.replace("=\n", "").into_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut index = 0;
+        while index < bytes.len() {
+            let escaped = (bytes[index] == b'=')
+                .then(|| bytes.get(index + 1..index + 3))
+                .flatten()
+                .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+            match escaped {
+                Some(byte) => {
+                    decoded.push(byte);
+                    index += 3;
+                }
+                None => {
+                    decoded.push(bytes[index]);
+                    index += 1;
+                }
+            }
+        }
+        String::from_utf8(decoded).unwrap()
+    }
+
+    /// The token from the first `{path}?token=` link in the message.
+    fn token_after(message: &str, path: &str) -> String {
+        let start = message.find(&format!("{path}?token=")).expect("link in message") + path.len() + 7;
+        message[start..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '.' || *c == '_')
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn confirmation_link_round_trips_through_smtp() {
+        configure();
+        let (mailer, mut received) = smtp_server(&[]).await;
+
+        send(
+            &mailer,
+            confirmation_email("reader@example.com".to_string(), "abc123").unwrap(),
+        )
+        .await
+        .unwrap();
+        let message = decode_quoted_printable(&received.recv().await.unwrap());
+
+        assert!(message.contains("To: reader@example.com"));
+        let token = token_after(&message, "/newsletter/confirm");
+        assert_eq!(
+            verify_token(&token, CONFIRM).unwrap(),
+            RecordId::new("subscriber", "abc123")
+        );
+        // A confirmation link can't be used to unsubscribe someone.
+        assert!(verify_token(&token, UNSUBSCRIBE).is_err());
+    }
+
+    #[tokio::test]
+    async fn digest_carries_a_working_unsubscribe_link() {
+        configure();
+        let (mailer, mut received) = smtp_server(&[]).await;
+        let post = NewPost {
+            id: RecordId::new("post", "p1"),
+            title: "Async Rust".to_string(),
+            summary: "Futures all the way down.".to_string(),
+            slug: Some("async-rust".to_string()),
+        };
+        let recipient = Recipient {
+            id: RecordId::new("subscriber", "s1"),
+            email: "reader@example.com".to_string(),
+        };
+
+        send(&mailer, digest(&[&post], &recipient).unwrap()).await.unwrap();
+        let message = decode_quoted_printable(&received.recv().await.unwrap());
+
+        assert!(message.contains("Subject: Async Rust"));
+        assert!(message.contains("/post/async-rust"));
+        assert!(message.contains("List-Unsubscribe-Post: List-Unsubscribe=One-Click"));
+        let token = token_after(&message, "/newsletter/unsubscribe");
+        assert_eq!(verify_token(&token, UNSUBSCRIBE).unwrap(), recipient.id);
+        assert!(verify_token(&token, CONFIRM).is_err());
+    }
+
+    #[tokio::test]
+    async fn rejected_recipients_fail_the_send() {
+        configure();
+        let (mailer, mut received) = smtp_server(&["gone@example.com"]).await;
+
+        let email = confirmation_email("gone@example.com".to_string(), "abc123").unwrap();
+        assert!(send(&mailer, email).await.is_err());
+        assert!(received.try_recv().is_err());
+    }
+
+    #[test]
+    fn expired_tampered_and_mismatched_tokens_are_rejected() {
+        configure();
+        let now = chrono::Utc::now().timestamp();
+
+        let expired = issue_token("abc123", CONFIRM, now - 1).unwrap();
+        assert!(verify_token(&expired, CONFIRM).is_err());
+
+        let valid = issue_token("abc123", CONFIRM, now + 60).unwrap();
+        assert!(verify_token(&valid, CONFIRM).is_ok());
+        assert!(verify_token(&valid, UNSUBSCRIBE).is_err());
+        assert!(verify_token(&valid.replace("abc123", "abc124"), CONFIRM).is_err());
+        assert!(verify_token(&format!("{valid}0"), CONFIRM).is_err());
+        assert!(verify_token("", CONFIRM).is_err());
+
+        // Unsubscribe links never expire.
+        let unsubscribe = issue_token("abc123", UNSUBSCRIBE, 0).unwrap();
+        assert!(verify_token(&unsubscribe, UNSUBSCRIBE).is_ok());
+    }
+}
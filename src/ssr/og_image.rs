@@ -0,0 +1,353 @@
+use axum::body::Body;
+use axum::extract::Path;
+use axum::http::{Response, StatusCode};
+use resvg::{tiny_skia, usvg};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
+use surrealdb_types::SurrealValue;
+
+use crate::seo::site;
+use crate::ssr::app_state::db;
+use crate::ssr::server_utils::{escape_html, validate_slug};
+
+const WIDTH: u32 = 1200;
+const HEIGHT: u32 = 627;
+const PADDING: f32 = 80.0;
+
+/// The fonts are bundled, so cards look the same on every host and never
+/// depend on what is installed there.
+static FONTS: LazyLock<Arc<usvg::fontdb::Database>> = LazyLock::new(|| {
+    let mut fonts = usvg::fontdb::Database::new();
+    fonts.load_font_data(include_bytes!("../../public/fonts/KaTeX_SansSerif-Bold.ttf").to_vec());
+    fonts.load_font_data(include_bytes!("../../public/fonts/KaTeX_Typewriter-Regular.ttf").to_vec());
+    Arc::new(fonts)
+});
+
+static CACHE_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+    env::var("OG_IMAGE_CACHE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| env::temp_dir().join("og-images"))
+});
+
+#[derive(SurrealValue)]
+struct Card {
+    title: String,
+    tags: Vec<String>,
+    /// Filled in by the `post_read_time` event, so missing until it has run.
+    read_time: Option<usize>,
+}
+
+impl Card {
+    /// Changes whenever anything drawn on the card does, so an edited post
+    /// gets a new file instead of a stale cached one.
+    fn fingerprint(&self) -> String {
+        let site = site();
+        let mut hasher = Sha256::new();
+        for part in [
+            &self.title,
+            &self.tags.join(","),
+            &self
+                .read_time
+                .map(|read_time| read_time.to_string())
+                .unwrap_or_default(),
+            &site.url,
+            &site.short_name,
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        hex::encode(&hasher.finalize()[..8])
+    }
+}
+
+/// `/og/{slug}.png`: a 1200×627 share card for posts without a header image.
+pub async fn og_image_handler(Path(file): Path<String>) -> Response<Body> {
+    let status = |status: StatusCode| Response::builder().status(status).body(Body::empty()).unwrap();
+
+    let Some(slug) = file.strip_suffix(".png") else {
+        return status(StatusCode::NOT_FOUND);
+    };
+    if validate_slug(slug).is_err() {
+        return status(StatusCode::NOT_FOUND);
+    }
+
+    let db = db().await;
+    let card = match db
+        .query("SELECT title, tags, read_time FROM post WHERE slug = $slug AND fn::post::is_live(is_published, publish_at);")
+        .bind(("slug", slug.to_string()))
+        .await
+    {
+        Ok(mut query) => query.take::<Vec<Card>>(0).ok().and_then(|cards| cards.into_iter().next()),
+        Err(_) => None,
+    };
+    let Some(card) = card else {
+        return status(StatusCode::NOT_FOUND);
+    };
+
+    let fingerprint = card.fingerprint();
+    let path = CACHE_DIR.join(format!("{slug}-{fingerprint}.png"));
+    let png = match tokio::fs::read(&path).await {
+        Ok(png) => png,
+        Err(_) => match tokio::task::spawn_blocking(move || render(&card)).await {
+            Ok(Ok(png)) => {
+                match store(&path, &png).await {
+                    Ok(()) => {
+                        if let Err(err) = prune(&CACHE_DIR, slug, &fingerprint).await {
+                            tracing::warn!("failed to prune og images for {slug}: {err}");
+                        }
+                    }
+                    Err(err) => tracing::warn!("failed to cache {}: {err}", path.display()),
+                }
+                png
+            }
+            Ok(Err(err)) => {
+                tracing::error!("failed to render og image for {slug}: {err}");
+                return status(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+            Err(err) => {
+                tracing::error!("og image render task failed for {slug}: {err}");
+                return status(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Response::builder()
+        .header("Content-Type", "image/png")
+        .header("Cache-Control", "public, max-age=86400")
+        .body(Body::from(png))
+        .unwrap()
+}
+
+/// Writes through a temporary file so a concurrent request never reads a
+/// half-written image.
+async fn store(path: &std::path::Path, png: &[u8]) -> std::io::Result<()> {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+
+    tokio::fs::create_dir_all(&*CACHE_DIR).await?;
+    let tmp = path.with_extension(format!(
+        "{}.{}.tmp",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ));
+    tokio::fs::write(&tmp, png).await?;
+    tokio::fs::rename(&tmp, path).await
+}
+
+/// Removes the cards cached for `slug` under an older fingerprint.
+async fn prune(dir: &std::path::Path, slug: &str, fingerprint: &str) -> std::io::Result<()> {
+    let prefix = format!("{slug}-");
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name();
+        let stale = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(&prefix)?.strip_suffix(".png"))
+            .is_some_and(|other| {
+                other != fingerprint && other.len() == fingerprint.len() && other.bytes().all(|b| b.is_ascii_hexdigit())
+            });
+        if stale {
+            tokio::fs::remove_file(entry.path()).await?;
+        }
+    }
+    Ok(())
+}
+
+fn render(card: &Card) -> Result<Vec<u8>, String> {
+    let options = usvg::Options {
+        fontdb: FONTS.clone(),
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(&svg(card), &options).map_err(|err| err.to_string())?;
+    let mut pixmap = tiny_skia::Pixmap::new(WIDTH, HEIGHT).ok_or("invalid image size")?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    pixmap.encode_png().map_err(|err| err.to_string())
+}
+
+/// The card in the site's dark palette: host on top, the title in up to
+/// three lines, tags, read time and brand along the bottom.
+fn svg(card: &Card) -> String {
+    let site = site();
+    let host = escape_html(site.url.trim_start_matches("https://").trim_start_matches("http://"));
+
+    let chars = card.title.chars().count();
+    let font_size = match chars {
+        0..=40 => 72.0,
+        41..=80 => 60.0,
+        _ => 50.0,
+    };
+    let line_height = font_size * 1.2;
+    let title = wrap(
+        &card.title,
+        ((WIDTH as f32 - 2.0 * PADDING) / (font_size * 0.5)) as usize,
+        3,
+    )
+    .iter()
+    .enumerate()
+    .map(|(index, line)| {
+        format!(
+            r#"<tspan x="{PADDING}" y="{}">{}</tspan>"#,
+            220.0 + index as f32 * line_height,
+            escape_html(line)
+        )
+    })
+    .collect::<String>();
+
+    let tags = card
+        .tags
+        .iter()
+        .take(4)
+        .map(|tag| format!("#{}", tag.trim().to_lowercase()))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}">
+  <rect width="{WIDTH}" height="{HEIGHT}" fill="#0b0b0c"/>
+  <rect x="40" y="40" width="{inner_width}" height="{inner_height}" rx="16" fill="#141417" stroke="#2a2a2f" stroke-width="2" stroke-dasharray="8 8"/>
+  <rect x="40" y="40" width="12" height="{inner_height}" rx="6" fill="#f0653a"/>
+  <text x="{PADDING}" y="120" font-family="KaTeX_Typewriter" font-size="28" fill="#9b9ba3">// {host}</text>
+  <text font-family="KaTeX_SansSerif" font-weight="bold" font-size="{font_size}" fill="#ededee">{title}</text>
+  <text x="{PADDING}" y="540" font-family="KaTeX_Typewriter" font-size="28" fill="#f0653a">{tags}</text>
+  <text x="{PADDING}" y="490" font-family="KaTeX_Typewriter" font-size="24" fill="#9b9ba3">{read_time}</text>
+  <text x="{brand_x}" y="540" text-anchor="end" font-family="KaTeX_SansSerif" font-weight="bold" font-size="36" fill="#ededee">{brand}</text>
+</svg>"##,
+        inner_width = WIDTH - 80,
+        inner_height = HEIGHT - 80,
+        read_time = card
+            .read_time
+            .map(|read_time| format!("{read_time}min read"))
+            .unwrap_or_default(),
+        brand_x = WIDTH as f32 - PADDING,
+        tags = escape_html(&tags),
+        brand = escape_html(&site.short_name),
+    )
+}
+
+/// Greedy word wrap to `width` characters, ending the last line with an
+/// ellipsis when the text doesn't fit in `max_lines`.
+fn wrap(text: &str, width: usize, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        let last = &mut lines[max_lines - 1];
+        while last.chars().count() + 3 > width {
+            last.pop();
+        }
+        *last = format!("{}...", last.trim_end());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card() -> Card {
+        Card {
+            title: "Async Rust".to_string(),
+            tags: vec!["rust".to_string(), "async".to_string()],
+            read_time: Some(5),
+        }
+    }
+
+    #[test]
+    fn wrap_breaks_between_words() {
+        assert_eq!(wrap("one two three four", 9, 3), ["one two", "three", "four"]);
+        assert_eq!(wrap("  spaced   out  ", 20, 3), ["spaced out"]);
+        assert_eq!(wrap("", 10, 3), Vec::<String>::new());
+    }
+
+    #[test]
+    fn wrap_keeps_overlong_words_whole() {
+        assert_eq!(
+            wrap("a supercalifragilistic word", 10, 3),
+            ["a", "supercalifragilistic", "word"]
+        );
+    }
+
+    #[test]
+    fn wrap_ellipsizes_what_does_not_fit() {
+        let lines = wrap("one two three four five six", 9, 2);
+
+        assert_eq!(lines, ["one two", "three..."]);
+        assert!(lines.iter().all(|line| line.chars().count() <= 9));
+    }
+
+    #[test]
+    fn fingerprint_follows_what_is_drawn() {
+        let original = card().fingerprint();
+        assert_eq!(card().fingerprint(), original);
+
+        let retitled = Card {
+            title: "Async Rust, revisited".to_string(),
+            ..card()
+        };
+        let retagged = Card {
+            tags: vec!["rust".to_string()],
+            ..card()
+        };
+        let reordered = Card {
+            tags: vec!["async".to_string(), "rust".to_string()],
+            ..card()
+        };
+        let unread = Card {
+            read_time: None,
+            ..card()
+        };
+        for other in [retitled, retagged, reordered, unread] {
+            assert_ne!(other.fingerprint(), original, "{}", other.title);
+        }
+    }
+
+    #[test]
+    fn cards_render_without_a_read_time() {
+        let card = Card {
+            read_time: None,
+            ..card()
+        };
+
+        assert!(!svg(&card).contains("min read"));
+        assert!(render(&card).is_ok());
+    }
+
+    #[tokio::test]
+    async fn prune_removes_only_stale_cards_of_the_slug() {
+        let dir = env::temp_dir().join(format!("og-prune-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let files = [
+            "async-rust-00112233aabbccdd.png",
+            "async-rust-ffeeddccbbaa9988.png",
+            "async-rust-2-ffeeddccbbaa9988.png",
+            "other-ffeeddccbbaa9988.png",
+        ];
+        for file in files {
+            tokio::fs::write(dir.join(file), b"png").await.unwrap();
+        }
+
+        prune(&dir, "async-rust", "00112233aabbccdd").await.unwrap();
+
+        let exists = |file: &str| dir.join(file).exists();
+        assert!(exists(files[0]));
+        assert!(!exists(files[1]));
+        assert!(exists(files[2]));
+        assert!(exists(files[3]));
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
@@ -0,0 +1,86 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use chrono::Utc;
+use surrealdb_types::{Datetime, RecordId, SurrealValue};
+use tokio::sync::broadcast;
+
+use crate::ssr::app_state::db;
+
+/// Upper bound on how long the publisher sleeps, so posts scheduled while it
+/// is waiting are still picked up close to their `publish_at`.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+const RETRY_DELAY: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, SurrealValue)]
+pub struct PublishedPost {
+    pub id: RecordId,
+    pub title: String,
+    pub slug: Option<String>,
+}
+
+static PUBLISHED: LazyLock<broadcast::Sender<PublishedPost>> = LazyLock::new(|| broadcast::channel(64).0);
+
+/// Receives every scheduled post at the moment it goes live. The newsletter
+/// hooks in here (`newsletter::send_on_publish`); pages and feeds read
+/// `fn::post::is_live` on every request, so there is no cache to invalidate.
+pub fn subscribe() -> broadcast::Receiver<PublishedPost> {
+    PUBLISHED.subscribe()
+}
+
+/// Claims scheduled posts whose `publish_at` has passed by stamping
+/// `announced_at`, so each one is announced once even across restarts.
+async fn announce_due_posts() -> surrealdb::Result<Vec<PublishedPost>> {
+    let db = db().await;
+    let mut query = db
+        .query(
+            "UPDATE post SET announced_at = time::now()
+                WHERE is_published = true AND publish_at != NONE AND publish_at <= time::now() AND announced_at = NONE
+                RETURN id, title, slug;",
+        )
+        .await?;
+
+    query.take::<Vec<PublishedPost>>(0)
+}
+
+async fn next_publish_at() -> surrealdb::Result<Option<Datetime>> {
+    let db = db().await;
+    let mut query = db
+        .query(
+            "SELECT VALUE publish_at FROM post
+                WHERE is_published = true AND publish_at > time::now() AND announced_at = NONE
+                ORDER BY publish_at LIMIT 1;",
+        )
+        .await?;
+
+    Ok(query.take::<Vec<Datetime>>(0)?.into_iter().next())
+}
+
+pub async fn run() {
+    loop {
+        match announce_due_posts().await {
+            Ok(posts) => {
+                for post in posts {
+                    tracing::info!("post {} went live", post.slug.as_deref().unwrap_or(&post.title));
+                    // Sending only fails when nobody has subscribed.
+                    let _ = PUBLISHED.send(post);
+                }
+            }
+            Err(err) => {
+                tracing::error!("publisher failed to announce posts: {err}");
+                tokio::time::sleep(RETRY_DELAY).await;
+                continue;
+            }
+        }
+
+        let sleep = match next_publish_at().await {
+            Ok(Some(publish_at)) => (*publish_at - Utc::now()).to_std().unwrap_or_default().min(MAX_SLEEP),
+            Ok(None) => MAX_SLEEP,
+            Err(err) => {
+                tracing::error!("publisher failed to load the schedule: {err}");
+                RETRY_DELAY
+            }
+        };
+        tokio::time::sleep(sleep).await;
+    }
+}
@@ -1,18 +1,26 @@
-use super::types::Post;
+use super::types::{Post, RenderDiagnostic, RenderedMarkdown, TocEntry};
+use axum::extract::Path;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::Response;
-use chrono::{DateTime, Utc};
-use dioxus::prelude::Result;
+use chrono::{DateTime, FixedOffset, Utc};
+use dioxus::prelude::{HttpError, Result};
 use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd, TextMergeStream};
 use regex::Regex;
-use rss::{ChannelBuilder, Item};
+use rss::{Category, ChannelBuilder, Guid, Item};
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
 use surrealdb::engine::remote::http::{Client, Http, Https};
 use surrealdb::opt::auth::{Database, Root};
 use surrealdb::Surreal;
-use syntect::highlighting::ThemeSet;
+use surrealdb_types::{RecordId, SurrealValue, ToSql};
+use syntect::highlighting::{Theme, ThemeSet};
 use syntect::html::highlighted_html_for_string;
 use syntect::parsing::SyntaxSet;
 
+use crate::seo::{site, tag_path};
 use crate::ssr::app_state::db;
 
 pub async fn connect() -> Surreal<Client> {
@@ -52,53 +60,646 @@ pub async fn connect() -> Surreal<Client> {
     db
 }
 
+/// Slugs are produced by `string::slug`, so anything outside `[a-z0-9_-]` can't
+/// match a post and is rejected before it reaches the database.
+pub fn validate_slug(slug: &str) -> Result<(), HttpError> {
+    let valid = !slug.is_empty()
+        && slug.len() <= 256
+        && slug
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_');
+
+    if valid {
+        Ok(())
+    } else {
+        HttpError::bad_request("invalid slug")
+    }
+}
+
+/// Builds a typed record id from the key the client sends, rejecting anything
+/// that isn't a plain generated key.
+pub fn record_id(table: &str, key: &str) -> Result<RecordId, HttpError> {
+    let valid = !key.is_empty() && key.len() <= 64 && key.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_');
+
+    if valid {
+        Ok(RecordId::new(table, key))
+    } else {
+        HttpError::bad_request(format!("invalid {table} id"))
+    }
+}
+
+/// Tags are free-form on the post, so lookups compare against the trimmed,
+/// lowercased form that the tag links are built from.
+pub fn normalize_tag(tag: &str) -> Result<String, HttpError> {
+    let tag = tag.trim().to_lowercase();
+    let valid = !tag.is_empty() && tag.len() <= 64 && !tag.chars().any(char::is_control);
+
+    if valid {
+        Ok(tag)
+    } else {
+        HttpError::bad_request("invalid tag")
+    }
+}
+
+/// Markers handed to `search::highlight`. Control characters can't appear in
+/// post content, so they survive HTML escaping and are swapped for `<mark>`.
+pub const HIGHLIGHT_START: &str = "\u{2}";
+pub const HIGHLIGHT_END: &str = "\u{3}";
+
+const SNIPPET_CONTEXT_BEFORE: usize = 80;
+const SNIPPET_CONTEXT_AFTER: usize = 200;
+
+/// Reverse proxies in front of the app (`TRUSTED_PROXIES`, default 0), each
+/// appending the address it received the request from to `X-Forwarded-For`.
+static TRUSTED_PROXIES: LazyLock<usize> = LazyLock::new(|| {
+    env::var("TRUSTED_PROXIES")
+        .ok()
+        .and_then(|proxies| proxies.parse().ok())
+        .unwrap_or(0)
+});
+
+/// The visitor's IP. Without trusted proxies that is the peer address. With
+/// them, it is the `X-Forwarded-For` entry the outermost trusted proxy
+/// added; everything left of it came from the client and can be forged.
+pub fn client_ip(headers: &HeaderMap, peer: Option<IpAddr>) -> Option<String> {
+    forwarded_client(headers, peer, *TRUSTED_PROXIES)
+}
+
+fn forwarded_client(headers: &HeaderMap, peer: Option<IpAddr>, trusted_proxies: usize) -> Option<String> {
+    let mut hops = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|hop| hop.trim().to_string())
+        .filter(|hop| !hop.is_empty())
+        .collect::<Vec<_>>();
+    hops.extend(peer.map(|peer| peer.to_string()));
+
+    // A shorter chain didn't pass through all the proxies, so only the peer
+    // address can be trusted.
+    match hops.len().checked_sub(trusted_proxies + 1) {
+        Some(client) => hops.into_iter().nth(client),
+        None => peer.map(|peer| peer.to_string()),
+    }
+}
+
+/// `client_ip` for the request a server function is handling.
+pub async fn request_ip() -> Option<String> {
+    use axum::extract::ConnectInfo;
+    use dioxus::fullstack::FullstackContext;
+
+    let headers = FullstackContext::extract::<HeaderMap, _>().await.unwrap_or_default();
+    let peer = FullstackContext::extract::<ConnectInfo<SocketAddr>, _>()
+        .await
+        .ok()
+        .map(|ConnectInfo(addr)| addr.ip());
+    client_ip(&headers, peer)
+}
+
+/// A per-IP budget of `limit` requests per sliding `window`. In-process only,
+/// which is enough for a single instance; a restart resets the limits.
+pub struct RateLimit {
+    limit: usize,
+    window: Duration,
+    message: &'static str,
+    hits: tokio::sync::RwLock<Option<HashMap<String, Vec<Instant>>>>,
+}
+
+impl RateLimit {
+    pub const fn new(limit: usize, window: Duration, message: &'static str) -> Self {
+        Self {
+            limit,
+            window,
+            message,
+            hits: tokio::sync::RwLock::const_new(None),
+        }
+    }
+
+    /// Records a request from `ip`, or returns 429 with `message` once it has
+    /// used up the limit within the window.
+    pub async fn check(&self, ip: &str) -> Result<(), HttpError> {
+        let now = Instant::now();
+        let mut hits = self.hits.write().await;
+        let hits = hits.get_or_insert_with(HashMap::new);
+        hits.retain(|_, times| {
+            times.retain(|time| now.duration_since(*time) < self.window);
+            !times.is_empty()
+        });
+
+        let times = hits.entry(ip.to_string()).or_default();
+        if times.len() >= self.limit {
+            return Err(HttpError::new(StatusCode::TOO_MANY_REQUESTS, self.message));
+        }
+        times.push(now);
+
+        Ok(())
+    }
+}
+
+pub fn validate_email(email: &str) -> Result<(), HttpError> {
+    let valid = email.len() <= 254
+        && !email.chars().any(|c| c.is_whitespace() || c.is_control())
+        && email
+            .split_once('@')
+            .is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.') && !domain.starts_with('.'));
+
+    if !valid {
+        return HttpError::bad_request("invalid email address");
+    }
+    Ok(())
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes highlighted search output and turns the markers into `<mark>` tags.
+/// Markers that don't pair up, e.g. where a snippet cut through a match, are
+/// dropped or closed so the tags always balance.
+pub fn highlight_html(text: &str) -> String {
+    let mut open = false;
+    let mut balanced = String::with_capacity(text.len() + HIGHLIGHT_END.len());
+    for c in text.chars() {
+        let mut buf = [0; 4];
+        let marker: &str = c.encode_utf8(&mut buf);
+        if marker == HIGHLIGHT_START {
+            if open {
+                continue;
+            }
+            open = true;
+        } else if marker == HIGHLIGHT_END {
+            if !open {
+                continue;
+            }
+            open = false;
+        }
+        balanced.push(c);
+    }
+    if open {
+        balanced.push_str(HIGHLIGHT_END);
+    }
+
+    escape_html(&balanced)
+        .replace(HIGHLIGHT_START, "<mark>")
+        .replace(HIGHLIGHT_END, "</mark>")
+}
+
+/// Cuts a short window of highlighted text around the first match, collapsing
+/// whitespace so markdown line breaks don't leak into the result list.
+pub fn highlight_snippet(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let chars: Vec<char> = text.chars().collect();
+    let first_match = text
+        .find(HIGHLIGHT_START)
+        .map(|byte_index| text[..byte_index].chars().count())
+        .unwrap_or(0);
+
+    let start = first_match.saturating_sub(SNIPPET_CONTEXT_BEFORE);
+    let end = (first_match + SNIPPET_CONTEXT_AFTER).min(chars.len());
+    let window: String = chars[start..end].iter().collect();
+
+    let mut snippet = highlight_html(&window);
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
 pub async fn rss_handler() -> Response<String> {
     let db = db().await;
-    let rss = generate_rss(db).await.unwrap_or_default();
+    let rss = generate_rss(db, FeedFilter::All).await.unwrap_or_default();
+    Response::builder()
+        .header("Content-Type", "application/xml")
+        .body(rss)
+        .unwrap()
+}
+
+pub async fn atom_handler() -> Response<String> {
+    let db = db().await;
+    let atom = generate_atom(db).await.unwrap_or_default();
+    Response::builder()
+        .header("Content-Type", "application/atom+xml")
+        .body(atom)
+        .unwrap()
+}
+
+pub async fn json_feed_handler() -> Response<String> {
+    let db = db().await;
+    let feed = generate_json_feed(db).await.unwrap_or_default();
+    Response::builder()
+        .header("Content-Type", "application/feed+json")
+        .body(feed)
+        .unwrap()
+}
+
+pub async fn tag_rss_handler(Path(tag): Path<String>) -> Response<String> {
+    let Ok(tag) = normalize_tag(&tag) else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(String::new())
+            .unwrap();
+    };
+
+    let db = db().await;
+    let rss = generate_rss(db, FeedFilter::Tag(tag)).await.unwrap_or_default();
     Response::builder()
         .header("Content-Type", "application/xml")
         .body(rss)
         .unwrap()
 }
 
-pub async fn generate_rss(db: Surreal<Client>) -> Result<String> {
+pub async fn author_rss_handler(Path(id): Path<String>) -> Response<String> {
+    let not_found = || {
+        Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(String::new())
+            .unwrap()
+    };
+    let Ok(id) = record_id("author", &id) else {
+        return not_found();
+    };
+
+    let db = db().await;
+    let name = match db.query("SELECT VALUE name FROM $id;").bind(("id", id.clone())).await {
+        Ok(mut query) => query
+            .take::<Vec<String>>(0)
+            .ok()
+            .and_then(|names| names.into_iter().next()),
+        Err(_) => None,
+    };
+    let Some(name) = name else {
+        return not_found();
+    };
+
+    let rss = generate_rss(db, FeedFilter::Author { id, name })
+        .await
+        .unwrap_or_default();
+    Response::builder()
+        .header("Content-Type", "application/xml")
+        .body(rss)
+        .unwrap()
+}
+
+/// Which live posts a feed carries.
+pub enum FeedFilter {
+    All,
+    Tag(String),
+    Author { id: RecordId, name: String },
+}
+
+/// What the RSS, Atom and JSON feeds are built from.
+pub struct FeedContents {
+    pub title: String,
+    pub link: String,
+    pub description: String,
+    pub posts: Vec<Post>,
+}
+
+/// Live posts matching `filter`, newest first, with `body` replaced by the
+/// rendered HTML and `published_at` set like `select_post` does.
+async fn select_feed(db: Surreal<Client>, filter: FeedFilter) -> Result<FeedContents> {
+    let (tag, author) = match &filter {
+        FeedFilter::All => (None, None),
+        FeedFilter::Tag(tag) => (Some(tag.clone()), None),
+        FeedFilter::Author { id, .. } => (None, Some(id.clone())),
+    };
     let mut query = db
-        .query("SELECT *, author.*, <string>created_at AS created_at, <string>updated_at AS updated_at from post WHERE is_published = true ORDER BY created_at DESC;")
+        .query("SELECT *, author.*, <string>created_at AS created_at, <string>updated_at AS updated_at, <string>(edited_at ?? created_at) AS edited_at from post WHERE fn::post::is_live(is_published, publish_at) AND ($tag = NONE OR tags.map(|$t| string::lowercase(string::trim($t))) CONTAINS $tag) AND ($author = NONE OR author = $author) ORDER BY created_at DESC;")
+        .bind(("tag", tag))
+        .bind(("author", author))
         .await?;
     let mut posts = query.take::<Vec<Post>>(0)?;
 
     for post in &mut posts {
-        let date_time = DateTime::parse_from_rfc3339(&post.created_at)
-            .unwrap()
-            .with_timezone(&Utc);
-        post.created_at = date_time.to_rfc2822();
-        post.body = process_markdown(post.body.clone()).await?;
+        let created_at = DateTime::parse_from_rfc3339(&post.created_at)?.with_timezone(&Utc);
+        post.published_at = Some(
+            post.publish_at
+                .as_ref()
+                .map_or(created_at, |publish_at| **publish_at)
+                .to_rfc3339(),
+        );
+        post.body = render_post_body(post).await?.html;
     }
 
-    let channel = ChannelBuilder::default()
-        .title("Rust-DD")
-        .link("https://rust-dd.com")
-        .description("Rust-DD Blog – Tech Insights & Consulting")
+    let site = site();
+    let (title, link, description) = match &filter {
+        FeedFilter::All => (site.short_name.clone(), site.url.clone(), site.name.clone()),
+        FeedFilter::Tag(tag) => (
+            format!("{} · #{tag}", site.short_name),
+            site.absolute_url(&tag_path(tag)),
+            format!("Posts tagged #{tag} on {}", site.short_name),
+        ),
+        FeedFilter::Author { id, name } => (
+            format!("{} · {name}", site.short_name),
+            site.absolute_url(&format!("/author/{}", id.key.to_sql())),
+            format!("Posts by {name} on {}", site.short_name),
+        ),
+    };
+
+    Ok(FeedContents {
+        title,
+        link,
+        description,
+        posts,
+    })
+}
+
+/// A `tag:` URI (RFC 4151) from the post's record id, so feed readers keep
+/// recognizing a post after its slug or title changes.
+fn post_guid(post: &Post) -> String {
+    let host = site()
+        .url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .to_string();
+    let year = post.created_at.get(..4).unwrap_or("1970");
+    format!("tag:{host},{year}:post:{}", post.id.key.to_sql())
+}
+
+fn feed_date(value: &str) -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339(value).unwrap_or_default()
+}
+
+fn post_url(post: &Post) -> String {
+    site().absolute_url(&format!("/post/{}", post.slug.clone().unwrap_or_default()))
+}
+
+pub async fn generate_rss(db: Surreal<Client>, filter: FeedFilter) -> Result<String> {
+    Ok(rss_channel(&select_feed(db, filter).await?))
+}
+
+/// The RSS document for `feed`, whose post bodies are already rendered.
+pub fn rss_channel(feed: &FeedContents) -> String {
+    ChannelBuilder::default()
+        .title(feed.title.clone())
+        .link(feed.link.clone())
+        .description(feed.description.clone())
         .items(
-            posts
-                .into_iter()
+            feed.posts
+                .iter()
                 .map(|post| {
+                    let mut guid = Guid::default();
+                    guid.set_value(post_guid(post));
+                    guid.set_permalink(false);
+
                     let mut item = Item::default();
                     item.set_author(post.author.name.to_string());
                     item.set_title(post.title.to_string());
-                    item.set_description(post.body.to_string());
-                    item.set_link(format!("https://rust-dd.com/post/{}", post.slug.unwrap_or_default()));
-                    item.set_pub_date(post.created_at.to_string());
+                    item.set_description(post.summary.to_string());
+                    item.set_content(post.body.to_string());
+                    item.set_link(post_url(post));
+                    item.set_guid(guid);
+                    item.set_categories(
+                        post.tags
+                            .iter()
+                            .map(|tag| Category {
+                                name: tag.clone(),
+                                domain: None,
+                            })
+                            .collect::<Vec<_>>(),
+                    );
+                    item.set_pub_date(feed_date(post.published_at.as_deref().unwrap_or_default()).to_rfc2822());
                     item
                 })
                 .collect::<Vec<_>>(),
         )
-        .build();
+        .build()
+        .to_string()
+}
+
+pub async fn generate_atom(db: Surreal<Client>) -> Result<String> {
+    use atom_syndication::{Category, Content, Entry, Feed, Link, Person, Text};
+
+    let site = site();
+    let feed = select_feed(db, FeedFilter::All).await?;
+    let link = |href: String, rel: &str, mime_type: Option<&str>| Link {
+        href,
+        rel: rel.to_string(),
+        mime_type: mime_type.map(str::to_string),
+        ..Link::default()
+    };
+
+    let entries = feed
+        .posts
+        .iter()
+        .map(|post| Entry {
+            id: post_guid(post),
+            title: Text::plain(post.title.clone()),
+            updated: feed_date(&post.edited_at),
+            published: Some(feed_date(post.published_at.as_deref().unwrap_or_default())),
+            authors: vec![Person {
+                name: post.author.name.clone(),
+                uri: Some(site.absolute_url(&format!("/author/{}", post.author.id.key.to_sql()))),
+                ..Person::default()
+            }],
+            categories: post
+                .tags
+                .iter()
+                .map(|tag| Category {
+                    term: tag.clone(),
+                    ..Category::default()
+                })
+                .collect(),
+            links: vec![link(post_url(post), "alternate", Some("text/html"))],
+            summary: Some(Text::plain(post.summary.clone())),
+            content: Some(Content {
+                value: Some(post.body.clone()),
+                content_type: Some("html".to_string()),
+                ..Content::default()
+            }),
+            ..Entry::default()
+        })
+        .collect::<Vec<_>>();
+
+    let feed = Feed {
+        id: site.absolute_url("/atom.xml"),
+        title: Text::plain(feed.title),
+        subtitle: Some(Text::plain(feed.description)),
+        updated: entries.iter().map(|entry| entry.updated).max().unwrap_or_default(),
+        links: vec![
+            link(site.absolute_url("/atom.xml"), "self", Some("application/atom+xml")),
+            link(feed.link, "alternate", Some("text/html")),
+        ],
+        entries,
+        ..Feed::default()
+    };
+
+    Ok(feed.to_string())
+}
+
+/// JSON Feed 1.1 (https://www.jsonfeed.org/version/1.1/).
+pub async fn generate_json_feed(db: Surreal<Client>) -> Result<String> {
+    use serde_json::json;
+
+    let site = site();
+    let feed = select_feed(db, FeedFilter::All).await?;
+    let items = feed
+        .posts
+        .iter()
+        .map(|post| {
+            json!({
+                "id": post_guid(post),
+                "url": post_url(post),
+                "title": post.title,
+                "summary": post.summary,
+                "content_html": post.body,
+                "image": post.header_image,
+                "date_published": post.published_at,
+                "date_modified": feed_date(&post.edited_at).to_rfc3339(),
+                "authors": [{
+                    "name": post.author.name,
+                    "url": site.absolute_url(&format!("/author/{}", post.author.id.key.to_sql())),
+                }],
+                "tags": post.tags,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": feed.title,
+        "home_page_url": feed.link,
+        "feed_url": site.absolute_url("/feed.json"),
+        "description": feed.description,
+        "items": items,
+    })
+    .to_string())
+}
+
+// Loading the syntect defaults deserializes every bundled syntax and theme, so
+// they're loaded once per process instead of on every render.
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+static THEME: LazyLock<Theme> = LazyLock::new(|| {
+    let mut ts = ThemeSet::load_defaults();
+    ["base16-ocean.dark", "Solarized (dark)"]
+        .into_iter()
+        .find_map(|name| ts.themes.remove(name))
+        .or_else(|| ts.themes.into_values().next())
+        .expect("syntect default theme missing")
+});
+static RE_IMG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"!\[.*?\]\((.*?\.(svg|png|jpe?g|gif|bmp|webp))\)").expect("valid regex pattern"));
+static RE_BG_STYLES: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"background-color:\s*#[0-9a-fA-F]{6};?").expect("valid regex pattern"));
+static RE_EMPTY_STYLE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"style="\s*""#).expect("valid regex pattern"));
+
+#[derive(SurrealValue)]
+struct PostSource {
+    id: RecordId,
+    slug: Option<String>,
+    body: String,
+}
+
+impl PostSource {
+    fn name(&self) -> String {
+        self.slug.clone().unwrap_or_else(|| self.id.to_sql())
+    }
+}
+
+async fn select_post_sources() -> Result<Vec<PostSource>> {
+    let db = db().await;
+    let mut query = db.query("SELECT id, slug, body FROM post ORDER BY created_at;").await?;
+
+    Ok(query.take::<Vec<PostSource>>(0)?)
+}
+
+/// Returns the post's stored `body_html` and `toc`. Posts written outside the
+/// admin (seeds, manual queries) have none until they are first served, at
+/// which point they are rendered once and written back.
+pub async fn render_post_body(post: &Post) -> Result<RenderedMarkdown> {
+    if let Some(html) = &post.body_html {
+        return Ok(RenderedMarkdown {
+            html: html.clone(),
+            toc: post.toc.clone().unwrap_or_default(),
+            diagnostics: Vec::new(),
+        });
+    }
+
+    let rendered = process_markdown(post.body.clone()).await?;
+    store_rendered_body(post.id.clone(), post.body.clone(), &rendered).await?;
+
+    Ok(rendered)
+}
+
+/// Only writes when `body` is still the markdown the HTML was rendered from,
+/// so a concurrent edit is never overwritten with stale HTML.
+async fn store_rendered_body(id: RecordId, body: String, rendered: &RenderedMarkdown) -> Result<()> {
+    let db = db().await;
+    db.query("UPDATE $id SET body_html = $html, toc = $toc WHERE body = $body;")
+        .bind(("id", id))
+        .bind(("body", body))
+        .bind(("html", rendered.html.clone()))
+        .bind(("toc", rendered.toc.clone()))
+        .await?
+        .check()?;
+
+    Ok(())
+}
 
-    Ok(channel.to_string())
+/// Re-renders every post's `body_html`. Run after changing the markdown
+/// pipeline; returns how many posts were rendered and how many failed.
+pub async fn render_all_posts() -> Result<(usize, usize)> {
+    let (mut rendered, mut failed) = (0, 0);
+    for post in select_post_sources().await? {
+        let name = post.name();
+        let result = match process_markdown(post.body.clone()).await {
+            Ok(output) => {
+                for diagnostic in &output.diagnostics {
+                    tracing::warn!("{name}:{}: {}", diagnostic.line, diagnostic.message);
+                }
+                store_rendered_body(post.id, post.body, &output).await
+            }
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(()) => rendered += 1,
+            Err(err) => {
+                tracing::error!("failed to render {name}: {err}");
+                failed += 1;
+            }
+        }
+    }
+
+    Ok((rendered, failed))
 }
 
-pub async fn process_markdown(markdown: String) -> Result<String> {
+/// Renders every post without storing the result and returns each post's
+/// diagnostics, labelled by slug.
+pub async fn lint_posts() -> Result<Vec<(String, RenderDiagnostic)>> {
+    let mut found = Vec::new();
+    for post in select_post_sources().await? {
+        let name = post.name();
+        let output = process_markdown(post.body).await?;
+        found.extend(
+            output
+                .diagnostics
+                .into_iter()
+                .map(|diagnostic| (name.clone(), diagnostic)),
+        );
+    }
+
+    Ok(found)
+}
+
+/// Renders post markdown to HTML. Math that KaTeX rejects doesn't fail the
+/// render: it is shown inline as an error block and reported in
+/// `diagnostics`, so one bad expression can't take down a post or the feed.
+pub async fn process_markdown(markdown: String) -> Result<RenderedMarkdown> {
     struct MathEventProcessor {
         display_style_opts: katex::Opts,
     }
@@ -111,32 +712,60 @@ pub async fn process_markdown(markdown: String) -> Result<String> {
             }
         }
 
-        fn process_math_event<'a>(&'a self, event: Event<'a>) -> Event<'a> {
-            match event {
-                Event::InlineMath(math_exp) => Event::InlineHtml(CowStr::from(katex::render(&math_exp).unwrap())),
-                Event::DisplayMath(math_exp) => Event::Html(CowStr::from(
-                    katex::render_with_opts(&math_exp, &self.display_style_opts).unwrap(),
-                )),
-                _ => event,
+        fn process_math_event<'a>(
+            &'a self,
+            event: Event<'a>,
+            line: usize,
+            diagnostics: &mut Vec<RenderDiagnostic>,
+        ) -> Event<'a> {
+            let (math_exp, display) = match &event {
+                Event::InlineMath(math_exp) => (math_exp, false),
+                Event::DisplayMath(math_exp) => (math_exp, true),
+                _ => return event,
+            };
+
+            let rendered = if display {
+                katex::render_with_opts(math_exp, &self.display_style_opts)
+            } else {
+                katex::render(math_exp)
+            };
+            let html = rendered.unwrap_or_else(|err| {
+                let message = err.to_string();
+                let html = if display {
+                    format!(
+                        r#"<div class="math-error" title="{}"><code>$${}$$</code></div>"#,
+                        escape_html(&message),
+                        escape_html(math_exp)
+                    )
+                } else {
+                    format!(
+                        r#"<code class="math-error" title="{}">${}$</code>"#,
+                        escape_html(&message),
+                        escape_html(math_exp)
+                    )
+                };
+                diagnostics.push(RenderDiagnostic {
+                    line,
+                    source: math_exp.to_string(),
+                    message,
+                });
+                html
+            });
+
+            if display {
+                Event::Html(CowStr::from(html))
+            } else {
+                Event::InlineHtml(CowStr::from(html))
             }
         }
     }
 
-    let ps = SyntaxSet::load_defaults_newlines();
-    let ts = ThemeSet::load_defaults();
-    let theme = ts
-        .themes
-        .get("base16-ocean.dark")
-        .or_else(|| ts.themes.get("Solarized (dark)"))
-        .or_else(|| ts.themes.values().next())
-        .expect("syntect default theme missing");
-    let re_img = Regex::new(r"!\[.*?\]\((.*?\.(svg|png|jpe?g|gif|bmp|webp))\)")?;
-    let re_bg_styles = Regex::new(r"background-color:\s*#[0-9a-fA-F]{6};?")?;
-    let re_empty_style = Regex::new(r#"style="\s*""#)?;
+    let ps = &*SYNTAX_SET;
+    let theme = &*THEME;
 
     let mut processed_markdown = String::new();
     let mut last_img_end = 0;
-    for img_cap in re_img.captures_iter(&markdown) {
+    for img_cap in RE_IMG.captures_iter(&markdown) {
         processed_markdown.push_str(&markdown[last_img_end..img_cap.get(0).unwrap().start()]);
         let img_path = &img_cap[1];
         let img_format = &img_cap[2];
@@ -162,15 +791,26 @@ pub async fn process_markdown(markdown: String) -> Result<String> {
     options.insert(Options::ENABLE_STRIKETHROUGH);
     options.insert(Options::ENABLE_MATH);
 
-    let parser = Parser::new_ext(&processed_markdown, options);
+    // Image tags are rewritten on a single line, so line numbers in
+    // `processed_markdown` still match the source.
+    let mut diagnostics = Vec::new();
     let mep = MathEventProcessor::new();
-    let iterator = TextMergeStream::new(parser).map(|event| mep.process_math_event(event));
+    let parser = Parser::new_ext(&processed_markdown, options)
+        .into_offset_iter()
+        .map(|(event, range)| {
+            let line = processed_markdown[..range.start].matches('\n').count() + 1;
+            mep.process_math_event(event, line, &mut diagnostics)
+        });
+    let iterator = TextMergeStream::new(parser);
 
     let mut events = Vec::new();
     let mut in_code_block = false;
     let mut code_block_language: Option<String> = None;
     let mut code_block_content = String::new();
     let mut skip_image = false;
+    let mut heading: Option<(Tag, Vec<Event>)> = None;
+    let mut toc = Vec::new();
+    let mut anchors = HashSet::new();
 
     for event in iterator {
         if skip_image {
@@ -180,7 +820,47 @@ pub async fn process_markdown(markdown: String) -> Result<String> {
             continue;
         }
 
+        if let Some((_, content)) = heading.as_mut() {
+            if !matches!(event, Event::End(TagEnd::Heading(_))) {
+                content.push(event);
+                continue;
+            }
+        }
+
         match event {
+            Event::Start(tag @ Tag::Heading { .. }) => {
+                heading = Some((tag, Vec::new()));
+            }
+            Event::End(TagEnd::Heading(level)) => {
+                let Some((Tag::Heading { classes, attrs, .. }, content)) = heading.take() else {
+                    continue;
+                };
+                let text = content
+                    .iter()
+                    .filter_map(|event| match event {
+                        Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+                        _ => None,
+                    })
+                    .collect::<String>();
+                let anchor = unique_anchor(&text, &mut anchors);
+
+                events.push(Event::Start(Tag::Heading {
+                    level,
+                    id: Some(CowStr::from(anchor.clone())),
+                    classes,
+                    attrs,
+                }));
+                events.extend(content);
+                events.push(Event::InlineHtml(CowStr::from(format!(
+                    r##"<a class="heading-anchor" href="#{anchor}" aria-label="Link to this section">#</a>"##
+                ))));
+                events.push(Event::End(TagEnd::Heading(level)));
+                toc.push(TocEntry {
+                    level: level as u8,
+                    text: text.trim().to_string(),
+                    anchor,
+                });
+            }
             Event::Start(Tag::CodeBlock(kind)) => {
                 in_code_block = true;
                 code_block_content.clear();
@@ -197,9 +877,9 @@ pub async fn process_markdown(markdown: String) -> Result<String> {
                 let syntax = ps
                     .find_syntax_by_token(language)
                     .unwrap_or_else(|| ps.find_syntax_plain_text());
-                let mut highlighted_html = highlighted_html_for_string(&code_block_content, &ps, syntax, theme)?;
-                highlighted_html = re_bg_styles.replace_all(&highlighted_html, "").to_string();
-                highlighted_html = re_empty_style.replace_all(&highlighted_html, "").to_string();
+                let mut highlighted_html = highlighted_html_for_string(&code_block_content, ps, syntax, theme)?;
+                highlighted_html = RE_BG_STYLES.replace_all(&highlighted_html, "").to_string();
+                highlighted_html = RE_EMPTY_STYLE.replace_all(&highlighted_html, "").to_string();
                 events.push(Event::Html(CowStr::from(highlighted_html)));
                 code_block_language = None;
             }
@@ -214,7 +894,7 @@ pub async fn process_markdown(markdown: String) -> Result<String> {
             }
             Event::Start(Tag::Image { dest_url, .. }) => {
                 let img_path = dest_url.into_string();
-                let img_format = img_path.split('.').last().unwrap_or("").to_lowercase();
+                let img_format = img_path.split('.').next_back().unwrap_or("").to_lowercase();
 
                 let img_html = if img_format == "svg" {
                     format!(
@@ -231,10 +911,8 @@ pub async fn process_markdown(markdown: String) -> Result<String> {
                 events.push(Event::Html(CowStr::from(img_html)));
                 skip_image = true;
             }
-            Event::End(TagEnd::Image) => {
-                if !skip_image {
-                    events.push(Event::End(TagEnd::Image));
-                }
+            Event::End(TagEnd::Image) if !skip_image => {
+                events.push(Event::End(TagEnd::Image));
             }
             other if !in_code_block => events.push(other),
             _ => {}
@@ -245,7 +923,82 @@ pub async fn process_markdown(markdown: String) -> Result<String> {
     let mut html_output = String::new();
     push_html(&mut html_output, events.into_iter());
 
-    Ok(html_output)
+    Ok(RenderedMarkdown {
+        html: html_output,
+        toc,
+        diagnostics,
+    })
+}
+
+/// Renders reader-submitted markdown: the inline formatting, lists, quotes
+/// and code of `process_markdown`, without math, images, headings or tables.
+/// Raw HTML is escaped, and only `http(s)` and `mailto` links survive, marked
+/// `nofollow ugc` so comments can't be used for link spam.
+pub fn process_comment_markdown(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+
+    let mut events = Vec::new();
+    let mut links = Vec::new();
+    for event in TextMergeStream::new(Parser::new_ext(markdown, options)) {
+        match event {
+            Event::Html(html) | Event::InlineHtml(html) => events.push(Event::Text(html)),
+            Event::Start(Tag::Heading { .. }) => events.push(Event::Start(Tag::Paragraph)),
+            Event::End(TagEnd::Heading(_)) => events.push(Event::End(TagEnd::Paragraph)),
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                let url = dest_url.trim();
+                let allowed = ["https://", "http://", "mailto:"].iter().any(|scheme| {
+                    url.get(..scheme.len())
+                        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+                });
+                if allowed {
+                    events.push(Event::InlineHtml(CowStr::from(format!(
+                        r#"<a href="{}" rel="nofollow ugc noopener" target="_blank">"#,
+                        escape_html(url)
+                    ))));
+                }
+                links.push(allowed);
+            }
+            Event::End(TagEnd::Link) => {
+                if links.pop().unwrap_or(false) {
+                    events.push(Event::InlineHtml(CowStr::from("</a>")));
+                }
+            }
+            // Images become their alt text.
+            Event::Start(Tag::Image { .. }) | Event::End(TagEnd::Image) => {}
+            other => events.push(other),
+        }
+    }
+
+    let mut html_output = String::new();
+    pulldown_cmark::html::push_html(&mut html_output, events.into_iter());
+    html_output
+}
+
+/// Slugs heading text into an `id`, suffixing repeats (`setup`, `setup-1`)
+/// so every heading in a post gets a distinct anchor, even one whose own
+/// text slugs to an earlier suffixed anchor.
+fn unique_anchor(text: &str, anchors: &mut HashSet<String>) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in text.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if (c.is_whitespace() || c == '-' || c == '_') && !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = match slug.trim_end_matches('-') {
+        "" => "section".to_string(),
+        slug => slug.to_string(),
+    };
+
+    let mut anchor = slug.clone();
+    let mut suffix = 0;
+    while !anchors.insert(anchor.clone()) {
+        suffix += 1;
+        anchor = format!("{slug}-{suffix}");
+    }
+    anchor
 }
 
 pub async fn sitemap_handler() -> Response<String> {
@@ -258,33 +1011,81 @@ pub async fn sitemap_handler() -> Response<String> {
     }
 
     let db = db().await;
-    let query = db
-        .query("SELECT slug, <string>created_at AS created_at FROM post WHERE is_published = true ORDER BY created_at DESC;")
-        .await;
-    let posts = query.unwrap().take::<Vec<SitemapPost>>(0).unwrap();
+    let mut query = db
+        .query(
+            "
+        SELECT slug, <string>created_at AS created_at FROM post WHERE fn::post::is_live(is_published, publish_at) ORDER BY created_at DESC;
+        array::distinct((SELECT VALUE author FROM post WHERE fn::post::is_live(is_published, publish_at)));
+        SELECT VALUE id FROM reference WHERE is_published = true;
+        SELECT VALUE slug FROM project WHERE is_visible = true;
+        ",
+        )
+        .await
+        .unwrap();
+    let posts = query.take::<Vec<SitemapPost>>(0).unwrap();
+    let authors = query.take::<Vec<RecordId>>(1).unwrap();
+    let references = query.take::<Vec<RecordId>>(2).unwrap();
+    let projects = query.take::<Vec<String>>(3).unwrap();
     let mut sitemap = String::new();
     sitemap.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
     sitemap.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
 
+    let site = site();
     let static_urls = vec![
-        ("https://rust-dd.com/", "daily", "0.9"),
-        ("https://rust-dd.com/opensource", "weekly", "0.8"),
-        ("https://rust-dd.com/rss.xml", "daily", "0.5"),
-        ("https://rust-dd.com/sitemap.xml", "monthly", "0.5"),
+        ("/", "daily", "0.9"),
+        ("/projects", "monthly", "0.8"),
+        ("/opensource", "weekly", "0.8"),
+        ("/references", "monthly", "0.7"),
+        ("/contact", "monthly", "0.6"),
+        ("/rss.xml", "daily", "0.5"),
+        ("/sitemap.xml", "monthly", "0.5"),
     ];
 
-    for (url, freq, priority) in static_urls {
+    for (path, freq, priority) in static_urls {
         sitemap.push_str("<url>\n");
-        sitemap.push_str(&format!("<loc>{}</loc>\n", url));
+        sitemap.push_str(&format!("<loc>{}</loc>\n", site.absolute_url(path)));
         sitemap.push_str(&format!("<changefreq>{}</changefreq>\n", freq));
         sitemap.push_str(&format!("<priority>{}</priority>\n", priority));
         sitemap.push_str("</url>\n");
     }
 
+    for author in authors {
+        sitemap.push_str("<url>\n");
+        sitemap.push_str(&format!(
+            "<loc>{}</loc>\n",
+            site.absolute_url(&format!("/author/{}", author.key.to_sql()))
+        ));
+        sitemap.push_str("<changefreq>weekly</changefreq>\n");
+        sitemap.push_str("<priority>0.6</priority>\n");
+        sitemap.push_str("</url>\n");
+    }
+
+    for reference in references {
+        sitemap.push_str("<url>\n");
+        sitemap.push_str(&format!(
+            "<loc>{}</loc>\n",
+            site.absolute_url(&format!("/references/{}", reference.key.to_sql()))
+        ));
+        sitemap.push_str("<changefreq>monthly</changefreq>\n");
+        sitemap.push_str("<priority>0.6</priority>\n");
+        sitemap.push_str("</url>\n");
+    }
+
+    for project in projects {
+        sitemap.push_str("<url>\n");
+        sitemap.push_str(&format!(
+            "<loc>{}</loc>\n",
+            site.absolute_url(&format!("/projects/{project}"))
+        ));
+        sitemap.push_str("<changefreq>monthly</changefreq>\n");
+        sitemap.push_str("<priority>0.6</priority>\n");
+        sitemap.push_str("</url>\n");
+    }
+
     for post in posts {
         if let Some(slug) = post.slug {
             sitemap.push_str("<url>\n");
-            sitemap.push_str(&format!("<loc>https://rust-dd.com/post/{}</loc>\n", slug));
+            sitemap.push_str(&format!("<loc>{}</loc>\n", site.absolute_url(&format!("/post/{slug}"))));
             sitemap.push_str("<changefreq>monthly</changefreq>\n");
             sitemap.push_str("<priority>1.0</priority>\n");
             sitemap.push_str(&format!("<lastmod>{}</lastmod>\n", post.created_at));
@@ -299,10 +1100,363 @@ pub async fn sitemap_handler() -> Response<String> {
 }
 
 pub async fn robots_handler() -> Response<String> {
-    let mut robots = String::new();
-    robots.push_str("User-agent: *\nDisallow:\n\nAllow: /\n\nSitemap: https://rust-dd.com/sitemap.xml\n");
+    let robots = format!(
+        "User-agent: *\nDisallow: /admin\nDisallow: /newsletter\n\nAllow: /\n\nSitemap: {}\n",
+        site().absolute_url("/sitemap.xml")
+    );
     Response::builder()
         .header("Content-Type", "text/plain")
         .body(robots)
         .unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAYLOADS: &[&str] = &[
+        "' OR 1=1 --",
+        "\" OR true; --",
+        "x; DELETE post;",
+        "x; REMOVE TABLE post",
+        "post:x",
+        "post:⟨x⟩",
+        "x⟩; DELETE post; --",
+        "`x`",
+        "$auth",
+        "../admin",
+        "a b",
+        "x\0",
+    ];
+
+    #[test]
+    fn validate_slug_rejects_injection_payloads() {
+        for payload in PAYLOADS {
+            assert!(validate_slug(payload).is_err(), "accepted {payload:?}");
+        }
+        assert!(validate_slug("").is_err());
+        assert!(validate_slug(&"a".repeat(257)).is_err());
+        assert!(validate_slug("Async-Rust").is_err());
+        assert!(validate_slug("async-rüst").is_err());
+    }
+
+    #[test]
+    fn validate_slug_accepts_generated_slugs() {
+        for slug in ["async-rust", "rust_2024", "a", &"a".repeat(256)] {
+            assert!(validate_slug(slug).is_ok(), "rejected {slug:?}");
+        }
+    }
+
+    #[test]
+    fn unique_anchor_suffixes_repeated_headings() {
+        let mut anchors = HashSet::new();
+        let anchors = ["Foo", "foo", "FOO", "Foo 1", "Bar"].map(|text| unique_anchor(text, &mut anchors));
+
+        assert_eq!(anchors, ["foo", "foo-1", "foo-2", "foo-1-1", "bar"]);
+    }
+
+    #[test]
+    fn unique_anchor_keeps_non_ascii_and_falls_back_for_punctuation() {
+        let mut anchors = HashSet::new();
+
+        assert_eq!(unique_anchor("Größe & Maß", &mut anchors), "größe-maß");
+        assert_eq!(unique_anchor("Ünïcode — 日本語", &mut anchors), "ünïcode-日本語");
+        assert_eq!(unique_anchor("  what's `new`?  ", &mut anchors), "whats-new");
+        assert_eq!(unique_anchor("?!", &mut anchors), "section");
+        assert_eq!(unique_anchor("---", &mut anchors), "section-1");
+        assert_eq!(unique_anchor("", &mut anchors), "section-2");
+    }
+
+    #[tokio::test]
+    async fn toc_lists_headings_with_levels_and_matching_ids() {
+        let markdown = "# Intro
+
+## Setup
+
+### `cargo` install
+
+## Setup
+
+Text.
+"
+        .to_string();
+        let rendered = process_markdown(markdown).await.unwrap();
+
+        let toc = rendered
+            .toc
+            .iter()
+            .map(|entry| (entry.level, entry.text.as_str(), entry.anchor.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            toc,
+            [
+                (1, "Intro", "intro"),
+                (2, "Setup", "setup"),
+                (3, "cargo install", "cargo-install"),
+                (2, "Setup", "setup-1"),
+            ]
+        );
+        for entry in &rendered.toc {
+            assert!(
+                rendered.html.contains(&format!(r#"id="{}""#, entry.anchor)),
+                "{}",
+                rendered.html
+            );
+        }
+    }
+
+    /// Writes the search markers as `[` and `]` to keep the fixtures readable.
+    fn marked(text: &str) -> String {
+        text.replace('[', HIGHLIGHT_START).replace(']', HIGHLIGHT_END)
+    }
+
+    fn count_tags(html: &str) -> (usize, usize) {
+        (html.matches("<mark>").count(), html.matches("</mark>").count())
+    }
+
+    #[test]
+    fn highlight_html_escapes_text_and_marks_matches() {
+        assert_eq!(
+            highlight_html(&marked("<b>[Rust]</b> & [rust]")),
+            "&lt;b&gt;<mark>Rust</mark>&lt;/b&gt; &amp; <mark>rust</mark>"
+        );
+        assert_eq!(highlight_html("no match"), "no match");
+    }
+
+    #[test]
+    fn highlight_html_balances_unpaired_markers() {
+        assert_eq!(highlight_html(&marked("ust] and [rust")), "ust and <mark>rust</mark>");
+        assert_eq!(highlight_html(&marked("[a [b] c]")), "<mark>a b</mark> c");
+    }
+
+    #[test]
+    fn highlight_snippet_collapses_whitespace_around_the_match() {
+        assert_eq!(
+            highlight_snippet(&marked("Intro\n\n## [Rust]\n  is   fun")),
+            "Intro ## <mark>Rust</mark> is fun"
+        );
+    }
+
+    #[test]
+    fn highlight_snippet_windows_long_text_around_the_first_match() {
+        let before = "b ".repeat(100);
+        let after = "z ".repeat(200);
+        let snippet = highlight_snippet(&marked(&format!("{before}[match] {after}")));
+
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'), "{snippet}");
+        assert!(snippet.contains("<mark>match</mark>"), "{snippet}");
+        assert_eq!(
+            snippet.chars().filter(|c| *c == 'b').count(),
+            SNIPPET_CONTEXT_BEFORE / 2
+        );
+    }
+
+    #[test]
+    fn highlight_snippet_balances_tags_at_the_window_edges() {
+        let long_match = "x".repeat(SNIPPET_CONTEXT_AFTER);
+        let snippet = highlight_snippet(&marked(&format!("[{long_match}] tail")));
+        assert!(snippet.ends_with("</mark>…"), "{snippet}");
+        assert_eq!(count_tags(&snippet), (1, 1));
+
+        let cut_match = marked(&format!("{} tail] then [rust]", "x".repeat(10)));
+        let snippet = highlight_snippet(&cut_match);
+        assert_eq!(count_tags(&snippet), (1, 1), "{snippet}");
+        assert!(!snippet.starts_with("</mark>"), "{snippet}");
+    }
+
+    #[test]
+    fn record_id_rejects_injection_payloads() {
+        for payload in PAYLOADS {
+            assert!(record_id("post", payload).is_err(), "accepted {payload:?}");
+        }
+        assert!(record_id("post", "").is_err());
+        assert!(record_id("post", &"a".repeat(65)).is_err());
+        assert!(record_id("post", "äbc").is_err());
+        assert!(record_id("post", "a-b").is_err());
+    }
+
+    #[test]
+    fn record_id_keeps_the_table_it_was_given() {
+        let id = record_id("post", "abc123_XYZ").unwrap();
+        assert_eq!(id, RecordId::new("post", "abc123_XYZ"));
+        assert!(record_id("post", &"a".repeat(64)).is_ok());
+    }
+
+    #[test]
+    fn normalize_tag_rejects_empty_overlong_and_control_characters() {
+        assert!(normalize_tag("").is_err());
+        assert!(normalize_tag("   ").is_err());
+        assert!(normalize_tag(&"a".repeat(65)).is_err());
+        assert!(normalize_tag("rust\0").is_err());
+        assert!(normalize_tag("rust\n; DELETE post").is_err());
+    }
+
+    /// Tags are bound as query parameters, so quotes and `;` are harmless and
+    /// kept as written; only the case and surrounding space change.
+    #[test]
+    fn normalize_tag_keeps_payloads_as_plain_text() {
+        assert_eq!(normalize_tag(" Rust ").unwrap(), "rust");
+        assert_eq!(normalize_tag("' OR 1=1 --").unwrap(), "' or 1=1 --");
+        assert_eq!(normalize_tag("x; DELETE post;").unwrap(), "x; delete post;");
+        assert_eq!(normalize_tag("post:⟨X⟩").unwrap(), "post:⟨x⟩");
+        assert_eq!(normalize_tag("Ünïcode").unwrap(), "ünïcode");
+    }
+
+    fn forwarded_for(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn client_ip_is_the_peer_without_trusted_proxies() {
+        let peer = Some("203.0.113.7".parse().unwrap());
+        assert_eq!(
+            forwarded_client(&forwarded_for("198.51.100.1"), peer, 0).as_deref(),
+            Some("203.0.113.7")
+        );
+        assert_eq!(
+            forwarded_client(&HeaderMap::new(), peer, 0).as_deref(),
+            Some("203.0.113.7")
+        );
+    }
+
+    #[test]
+    fn client_ip_ignores_hops_the_client_forged() {
+        let proxy = Some("10.0.0.2".parse().unwrap());
+        // The client sent `X-Forwarded-For: 1.1.1.1`; the proxy appended the
+        // address it saw.
+        let headers = forwarded_for("1.1.1.1, 203.0.113.7");
+        assert_eq!(forwarded_client(&headers, proxy, 1).as_deref(), Some("203.0.113.7"));
+        assert_eq!(
+            forwarded_client(&forwarded_for("203.0.113.7"), proxy, 1).as_deref(),
+            Some("203.0.113.7")
+        );
+        // Two proxies: the outer one's entry is second from the right.
+        let headers = forwarded_for("1.1.1.1, 203.0.113.7, 10.0.0.1");
+        assert_eq!(forwarded_client(&headers, proxy, 2).as_deref(), Some("203.0.113.7"));
+    }
+
+    #[test]
+    fn client_ip_falls_back_to_the_peer_when_the_chain_is_short() {
+        let peer = Some("203.0.113.7".parse().unwrap());
+        assert_eq!(
+            forwarded_client(&HeaderMap::new(), peer, 1).as_deref(),
+            Some("203.0.113.7")
+        );
+        assert_eq!(forwarded_client(&HeaderMap::new(), None, 0), None);
+    }
+
+    #[test]
+    fn comment_markdown_escapes_raw_html() {
+        let html = process_comment_markdown("<script>alert(1)</script>\n\nhi <b onclick=\"x()\">there</b>");
+
+        assert!(!html.contains("<script"), "{html}");
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"), "{html}");
+        assert!(!html.contains("<b "), "{html}");
+        assert!(html.contains("&lt;b onclick=\"x()\"&gt;"), "{html}");
+    }
+
+    #[test]
+    fn comment_markdown_drops_unsafe_link_schemes() {
+        for markdown in [
+            "[click](javascript:alert(1))",
+            "[click](JavaScript:alert(1))",
+            "[click]( javascript:alert(1))",
+            "[click](data:text/html;base64,PHNjcmlwdD4=)",
+            "[click](vbscript:msgbox(1))",
+            "<javascript:alert(1)>",
+            "<data:text/html,<script>alert(1)</script>>",
+        ] {
+            let html = process_comment_markdown(markdown);
+            assert!(!html.contains("<a "), "{markdown} -> {html}");
+            assert!(!html.contains("<script"), "{markdown} -> {html}");
+        }
+    }
+
+    #[test]
+    fn comment_markdown_keeps_safe_links_as_nofollow() {
+        let html = process_comment_markdown(
+            "[docs](https://docs.rs) and <https://example.com> or [me](mailto:me@example.com)",
+        );
+
+        assert!(html.contains(r#"<a href="https://docs.rs" rel="nofollow ugc noopener" target="_blank">docs</a>"#));
+        assert!(html.contains(r#"<a href="https://example.com" rel="nofollow ugc noopener" target="_blank">"#));
+        assert!(html.contains(r#"<a href="mailto:me@example.com" rel="nofollow ugc noopener" target="_blank">"#));
+    }
+
+    #[test]
+    fn comment_markdown_turns_images_into_alt_text() {
+        let html = process_comment_markdown("![a cat](https://example.com/cat.png) ![x](javascript:alert(1))");
+
+        assert!(!html.contains("<img"), "{html}");
+        assert!(html.contains("a cat"), "{html}");
+    }
+
+    #[test]
+    fn comment_markdown_cannot_inject_link_attributes() {
+        for markdown in [
+            r#"[x](https://example.com "title\" onmouseover=\"alert(1)")"#,
+            r#"[x](<https://example.com/" onmouseover="alert(1)>)"#,
+            r#"[x](https://example.com/'onmouseover='alert(1))"#,
+        ] {
+            let html = process_comment_markdown(markdown);
+            let tag = &html[html.find("<a ").expect("link is kept")..];
+            let tag = &tag[..tag.find('>').unwrap()];
+            // href, rel and target, and nothing the comment could add.
+            assert_eq!(tag.matches('"').count(), 6, "{markdown} -> {html}");
+            assert!(!html.contains("title="), "{markdown} -> {html}");
+        }
+    }
+
+    #[test]
+    fn comment_markdown_flattens_headings() {
+        let html = process_comment_markdown("# Big\n\ntext");
+
+        assert!(!html.contains("<h1"), "{html}");
+        assert!(html.contains("<p>Big</p>"), "{html}");
+    }
+
+    #[tokio::test]
+    async fn malformed_math_renders_inline_error_with_its_line() {
+        let markdown = "# Title\n\nFine: $x^2$.\n\nBroken: $x^$ here.\n".to_string();
+        let rendered = process_markdown(markdown).await.unwrap();
+
+        assert!(
+            rendered.html.contains(r#"<code class="math-error" title=""#),
+            "{}",
+            rendered.html
+        );
+        assert!(rendered.html.contains("$x^$</code>"), "{}", rendered.html);
+        assert_eq!(rendered.diagnostics.len(), 1);
+        assert_eq!(rendered.diagnostics[0].line, 5);
+        assert_eq!(rendered.diagnostics[0].source, "x^");
+        assert!(!rendered.diagnostics[0].message.is_empty());
+    }
+
+    #[tokio::test]
+    async fn malformed_display_math_renders_block_error() {
+        let markdown = "Intro\n\n$$\n\\begin{x}\n$$\n".to_string();
+        let rendered = process_markdown(markdown).await.unwrap();
+
+        assert!(
+            rendered.html.contains(r#"<div class="math-error""#),
+            "{}",
+            rendered.html
+        );
+        assert_eq!(rendered.diagnostics.len(), 1);
+        assert_eq!(rendered.diagnostics[0].line, 3);
+    }
+
+    #[tokio::test]
+    async fn valid_math_renders_without_diagnostics() {
+        let markdown = "Inline $e^{i\\pi} + 1 = 0$ and\n\n$$\n\\int_0^1 x\\,dx\n$$\n".to_string();
+        let rendered = process_markdown(markdown).await.unwrap();
+
+        assert!(rendered.diagnostics.is_empty(), "{:?}", rendered.diagnostics);
+        assert!(!rendered.html.contains("math-error"));
+        // The display block wraps a `katex` span of its own.
+        assert_eq!(rendered.html.matches(r#"<span class="katex">"#).count(), 2);
+        assert_eq!(rendered.html.matches(r#"<span class="katex-display">"#).count(), 1);
+    }
+}
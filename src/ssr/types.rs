@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use surrealdb_types::{RecordId, SurrealValue};
+use surrealdb_types::{Datetime, RecordId, SurrealValue};
 
 #[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq)]
 pub struct Author {
@@ -26,6 +26,32 @@ impl Default for Author {
     }
 }
 
+/// Social fields hold either a full URL or a bare handle.
+fn social_url(base: &str, value: Option<&str>) -> Option<String> {
+    let value = value?.trim();
+    if value.is_empty() {
+        None
+    } else if value.starts_with("https://") || value.starts_with("http://") {
+        Some(value.to_string())
+    } else {
+        Some(format!("{base}{}", value.trim_start_matches('@')))
+    }
+}
+
+impl Author {
+    pub fn github_url(&self) -> Option<String> {
+        social_url("https://github.com/", self.github.as_deref())
+    }
+
+    pub fn twitter_url(&self) -> Option<String> {
+        social_url("https://x.com/", self.twitter.as_deref())
+    }
+
+    pub fn linkedin_url(&self) -> Option<String> {
+        social_url("https://www.linkedin.com/in/", self.linkedin.as_deref())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq)]
 pub struct Post {
     pub id: RecordId,
@@ -39,9 +65,21 @@ pub struct Post {
     pub slug: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// RFC 3339 time of the last edit in the admin editor.
+    pub edited_at: String,
     pub is_published: bool,
     pub header_image: Option<String>,
     pub show_cta: bool,
+    pub publish_at: Option<Datetime>,
+    /// Server-side render of `body`; never sent to the client, which gets
+    /// the HTML in `body` instead.
+    #[serde(skip)]
+    pub body_html: Option<String>,
+    /// Headings of the rendered body, stored with `body_html`.
+    pub toc: Option<Vec<TocEntry>>,
+    /// RFC 3339 publication time (`publish_at`, else `created_at`), set by
+    /// `select_post` for structured data since `created_at` is preformatted.
+    pub published_at: Option<String>,
 }
 
 impl Default for Post {
@@ -58,9 +96,14 @@ impl Default for Post {
             slug: None,
             created_at: String::new(),
             updated_at: String::new(),
+            edited_at: String::new(),
             is_published: true,
             header_image: None,
             show_cta: false,
+            publish_at: None,
+            body_html: None,
+            toc: None,
+            published_at: None,
         }
     }
 }
@@ -81,3 +124,228 @@ pub struct Reference {
     pub category: Option<String>,
     pub icon: Option<String>,
 }
+
+/// A product on `/projects`. `body` is markdown; `select_project` returns
+/// it rendered to HTML.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq)]
+pub struct Project {
+    pub id: RecordId,
+    pub slug: String,
+    pub name: String,
+    pub kind: String,
+    pub summary: String,
+    pub url: String,
+    pub tags: Vec<String>,
+    pub body: String,
+    pub screenshots: Vec<Screenshot>,
+    pub tech_stack: Vec<String>,
+    pub tech_stack_percentage: Vec<u8>,
+    /// Slugs of posts about the project.
+    pub related_posts: Vec<String>,
+    pub is_featured: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq)]
+pub struct Screenshot {
+    pub src: String,
+    pub caption: Option<String>,
+}
+
+/// A link to a post, for lists outside the blog itself.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq)]
+pub struct PostLink {
+    pub slug: Option<String>,
+    pub title: String,
+    pub summary: String,
+}
+
+/// A "keep reading" card under a post, from `select_related_posts`.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq)]
+pub struct RelatedPost {
+    pub id: RecordId,
+    pub slug: Option<String>,
+    pub title: String,
+    pub summary: String,
+    pub tags: Vec<String>,
+    pub created_at: String,
+    pub read_time: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectPage {
+    pub project: Project,
+    /// The live posts from `project.related_posts`, in that order.
+    pub related_posts: Vec<PostLink>,
+}
+
+/// A repo on the `/opensource` page. `stars` is the stored count, shown
+/// until GitHub has been reached.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq)]
+pub struct OssProject {
+    pub id: RecordId,
+    pub name: String,
+    pub description: String,
+    pub url: String,
+    pub github_repo: String,
+    pub stars: u32,
+    pub language: String,
+    pub topics: Vec<String>,
+}
+
+/// Numbers for an `OssProject` from the latest GitHub sync.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RepoStats {
+    pub stars: u32,
+    pub forks: u32,
+    pub open_issues: u32,
+    /// Tag of the latest release, if the repo has any.
+    pub latest_release: Option<String>,
+    /// RFC 3339 time of the last push.
+    pub pushed_at: Option<String>,
+    /// SPDX id, or the license name when GitHub can't tell.
+    pub license: Option<String>,
+    /// Stars at each daily snapshot, oldest first.
+    pub star_history: Vec<DailyCount>,
+    pub gained_this_month: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PostPage {
+    pub posts: Vec<Post>,
+    pub page: usize,
+    pub total_pages: usize,
+    pub total_posts: usize,
+}
+
+/// An `/author/:id` profile: the author and their live posts, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuthorPage {
+    pub author: Author,
+    pub posts: Vec<Post>,
+}
+
+/// A ranked `search_posts` hit. `title` and `snippet` are escaped HTML with
+/// the matched terms wrapped in `<mark>`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SearchResult {
+    pub slug: Option<String>,
+    pub title: String,
+    pub snippet: String,
+    pub tags: Vec<String>,
+    pub created_at: String,
+    pub read_time: usize,
+    pub score: f64,
+}
+
+/// The fields the admin editor can change. `body` is raw markdown and
+/// `author` is the key of an `author` record.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PostInput {
+    pub title: String,
+    pub summary: String,
+    pub body: String,
+    pub tags: Vec<String>,
+    pub author: String,
+    pub is_published: bool,
+    pub show_cta: bool,
+    pub header_image: Option<String>,
+    pub publish_at: Option<Datetime>,
+}
+
+/// A problem found while rendering a post's markdown. The post still renders;
+/// the offending expression is shown inline as an error block instead.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RenderDiagnostic {
+    /// 1-based line in the markdown source.
+    pub line: usize,
+    pub source: String,
+    pub message: String,
+}
+
+/// A heading in a rendered post. `anchor` is the heading's `id`, slugged
+/// from its text, so links to a section survive re-renders.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, SurrealValue, PartialEq)]
+pub struct TocEntry {
+    pub level: u8,
+    pub text: String,
+    pub anchor: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RenderedMarkdown {
+    pub html: String,
+    pub toc: Vec<TocEntry>,
+    pub diagnostics: Vec<RenderDiagnostic>,
+}
+
+/// A `/contact` form submission. `website` is a honeypot: the field is
+/// hidden from people, so anything in it came from a bot.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ContactInput {
+    pub name: String,
+    pub email: String,
+    pub message: String,
+    pub website: String,
+}
+
+/// An approved comment as shown under a post. `parent` is the top-level
+/// comment it replies to; `body_html` is sanitized at submit time.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq)]
+pub struct Comment {
+    pub id: RecordId,
+    pub parent: Option<RecordId>,
+    pub author_name: String,
+    pub body_html: String,
+    pub created_at: String,
+}
+
+/// A comment waiting in the `/admin/comments` moderation queue.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq)]
+pub struct PendingComment {
+    pub id: RecordId,
+    pub author_name: String,
+    pub email: Option<String>,
+    pub body_html: String,
+    pub created_at: String,
+    pub post_title: String,
+    pub post_slug: Option<String>,
+    pub is_reply: bool,
+}
+
+/// A comment form submission. `parent` is the key of the comment being
+/// replied to, and `website` is a honeypot like on `ContactInput`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CommentInput {
+    pub author_name: String,
+    pub email: String,
+    pub body: String,
+    pub parent: Option<String>,
+    pub website: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DailyCount {
+    /// `YYYY-MM-DD`, UTC.
+    pub day: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RankedCount {
+    pub label: String,
+    /// Site path for entries that link somewhere, e.g. top posts.
+    pub path: Option<String>,
+    pub count: usize,
+}
+
+/// The `/admin/analytics` dashboard. Daily series cover every day of the
+/// window, zero-filled, oldest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AnalyticsReport {
+    pub days: usize,
+    pub views: Vec<DailyCount>,
+    pub visitors: Vec<DailyCount>,
+    pub top_posts: Vec<RankedCount>,
+    pub top_referrers: Vec<RankedCount>,
+    pub feed_subscribers: Vec<DailyCount>,
+}
@@ -0,0 +1,78 @@
+use chrono::{NaiveDate, Utc};
+use dioxus::prelude::{OrHttpError, Result};
+use sha2::{Digest, Sha256};
+use surrealdb_types::RecordId;
+use tokio::sync::RwLock;
+
+use crate::ssr::app_state::db;
+
+/// Lowercase User-Agent fragments of crawlers, link previewers and HTTP
+/// libraries. Their requests are dropped before a view is recorded.
+const BOT_USER_AGENTS: &[&str] = &[
+    "bot",
+    "crawl",
+    "spider",
+    "slurp",
+    "facebookexternalhit",
+    "embedly",
+    "preview",
+    "headless",
+    "lighthouse",
+    "curl",
+    "wget",
+    "python-requests",
+    "go-http-client",
+    "httpclient",
+];
+
+static VIEW_SALT: RwLock<Option<(NaiveDate, String)>> = RwLock::const_new(None);
+
+pub fn is_bot(user_agent: &str) -> bool {
+    let user_agent = user_agent.to_lowercase();
+    user_agent.trim().is_empty() || BOT_USER_AGENTS.iter().any(|bot| user_agent.contains(bot))
+}
+
+/// Today's salt, created on first use and shared through the database so
+/// every instance hashes visitors the same way.
+async fn daily_salt(day: NaiveDate) -> Result<String> {
+    if let Some((salt_day, salt)) = VIEW_SALT.read().await.as_ref() {
+        if *salt_day == day {
+            return Ok(salt.clone());
+        }
+    }
+
+    let db = db().await;
+    let id = RecordId::new("view_salt", day.to_string());
+    let mut query = db
+        .query(
+            "
+        UPSERT $id SET salt = salt ?? rand::string(32) RETURN VALUE salt;
+        DELETE view_salt WHERE id != $id;
+        ",
+        )
+        .bind(("id", id))
+        .await?;
+    let salt = query
+        .take::<Option<String>>(0)?
+        .or_internal_server_error("view salt missing")?;
+
+    *VIEW_SALT.write().await = Some((day, salt.clone()));
+    Ok(salt)
+}
+
+/// Returns `(day, visitor)`, where `visitor` hashes the IP and User-Agent
+/// with the day's salt. The same visitor maps to a new hash every day.
+///
+/// `ip` must come from `client_ip`: an address the client can set itself
+/// would let one visitor count as many.
+pub async fn visitor_id(ip: &str, user_agent: &str) -> Result<(String, String)> {
+    let day = Utc::now().date_naive();
+    let salt = daily_salt(day).await?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(ip.as_bytes());
+    hasher.update(user_agent.as_bytes());
+
+    Ok((day.to_string(), hex::encode(hasher.finalize())))
+}